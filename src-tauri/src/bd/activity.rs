@@ -0,0 +1,618 @@
+//! Live activity stream from `bd activity --follow`.
+//!
+//! The stream runs as a background task, forwarding parsed events over an
+//! mpsc channel and restarting itself with exponential backoff when bd exits
+//! or the stream goes bad.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::events::DashboardEvent;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Consecutive connection failures before the stream gives up entirely.
+const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+/// Hard cap: a connection producing this many unparseable lines is torn down
+/// and restarted.
+const MAX_PARSE_ERRORS: u32 = 100;
+/// Soft cap: past this many parse errors we warn the UI (once per
+/// connection) that bd is likely emitting an incompatible event format.
+const PARSE_ERROR_WARN_THRESHOLD: u32 = 10;
+/// Longest slice of an unparseable line included in the warning.
+const PARSE_ERROR_SAMPLE_LEN: usize = 120;
+
+/// Default window over which rapid events for the same entity collapse.
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(200);
+
+/// One line of `bd activity --json` output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub event_type: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub issue_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Tuning knobs for the stream's reconnect behavior. [`Default`] matches
+/// the historical compile-time constants; deployments on flaky networks can
+/// trade faster retries against more bd churn.
+#[derive(Debug, Clone)]
+pub struct StreamConfig {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Consecutive connection failures before the stream gives up.
+    pub max_consecutive_errors: u32,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: INITIAL_BACKOFF,
+            max_backoff: MAX_BACKOFF,
+            max_consecutive_errors: MAX_CONSECUTIVE_ERRORS,
+        }
+    }
+}
+
+pub struct ActivityStream {
+    bd_path: PathBuf,
+    workspace: PathBuf,
+    /// Timestamp of the last event forwarded, used to resume with `--since`
+    /// after a reconnect so nothing that happened while disconnected is lost.
+    last_seen: Arc<Mutex<Option<String>>>,
+}
+
+/// What `LineParser` decided about a single stream line.
+#[derive(Debug)]
+enum LineOutcome {
+    Event(ActivityEvent),
+    /// Unparseable but below thresholds; drop it.
+    Skip,
+    /// The warning threshold was just crossed; payload is a truncated sample
+    /// of the offending line.
+    Warn(String),
+    /// The hard cap was hit; the connection should be restarted.
+    RestartStream,
+}
+
+/// Per-connection parse-error bookkeeping, split from the IO loop so the
+/// threshold behavior is testable.
+#[derive(Default)]
+struct LineParser {
+    /// Total unparseable lines on this connection. Deliberately not reset
+    /// on success: a steadily corrupt stream with good lines interspersed
+    /// should still hit [`MAX_PARSE_ERRORS`] and restart.
+    parse_errors: u32,
+    warned: bool,
+}
+
+impl LineParser {
+    fn handle_line(&mut self, line: &str) -> LineOutcome {
+        match serde_json::from_str::<ActivityEvent>(line) {
+            Ok(event) => LineOutcome::Event(event),
+            Err(_) => {
+                self.parse_errors += 1;
+                if self.parse_errors >= MAX_PARSE_ERRORS {
+                    return LineOutcome::RestartStream;
+                }
+                if self.parse_errors >= PARSE_ERROR_WARN_THRESHOLD && !self.warned {
+                    self.warned = true;
+                    return LineOutcome::Warn(truncate_sample(line));
+                }
+                LineOutcome::Skip
+            }
+        }
+    }
+}
+
+fn truncate_sample(line: &str) -> String {
+    if line.len() <= PARSE_ERROR_SAMPLE_LEN {
+        line.to_string()
+    } else {
+        let cut = line
+            .char_indices()
+            .take_while(|(i, _)| *i < PARSE_ERROR_SAMPLE_LEN)
+            .last()
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        format!("{}…", &line[..cut])
+    }
+}
+
+impl ActivityStream {
+    pub fn new(bd_path: impl Into<PathBuf>, workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            bd_path: bd_path.into(),
+            workspace: workspace.into(),
+            last_seen: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Handle on the resume cursor, for callers that want to observe or
+    /// pre-seed it (e.g. from a persisted cache).
+    pub fn last_seen_handle(&self) -> Arc<Mutex<Option<String>>> {
+        self.last_seen.clone()
+    }
+
+    /// Argument list for one `bd activity` connection; `--since` resumes
+    /// from the given timestamp.
+    fn build_stream_args(since: Option<&str>) -> Vec<String> {
+        let mut args = vec![
+            "activity".to_string(),
+            "--follow".to_string(),
+            "--json".to_string(),
+        ];
+        if let Some(ts) = since {
+            args.push("--since".to_string());
+            args.push(ts.to_string());
+        }
+        args
+    }
+
+    /// Spawn the stream task. Parsed events arrive on the returned receiver;
+    /// out-of-band notices (like the parse-error warning) go to `notices`
+    /// when provided. With `event_types` set, events of any other type are
+    /// dropped before they reach the channel (the resume cursor still
+    /// advances past them); `None` forwards everything.
+    pub fn start(
+        self,
+        notices: Option<mpsc::Sender<DashboardEvent>>,
+        event_types: Option<HashSet<String>>,
+        config: StreamConfig,
+    ) -> mpsc::Receiver<ActivityEvent> {
+        let (tx, rx) = mpsc::channel(256);
+        tokio::spawn(async move {
+            self.run(tx, notices, event_types, config).await;
+        });
+        rx
+    }
+
+    async fn run(
+        self,
+        tx: mpsc::Sender<ActivityEvent>,
+        notices: Option<mpsc::Sender<DashboardEvent>>,
+        event_types: Option<HashSet<String>>,
+        config: StreamConfig,
+    ) {
+        let mut backoff = config.initial_backoff;
+        let mut consecutive_errors = 0u32;
+        // Tracks whether we've told the UI we're connected, so only actual
+        // transitions produce ConnectionChanged events.
+        let mut connected = false;
+        // Cleared the first time a `--since` connection dies without reading
+        // a single line, which is what an unsupported flag looks like.
+        let mut since_supported = true;
+        loop {
+            let since = if since_supported {
+                self.last_seen.lock().unwrap().clone()
+            } else {
+                None
+            };
+            let result = self
+                .run_stream(
+                    &tx,
+                    notices.as_ref(),
+                    &mut connected,
+                    since.as_deref(),
+                    event_types.as_ref(),
+                )
+                .await;
+            let produced_output = connected;
+            match result {
+                Ok(()) => {
+                    backoff = config.initial_backoff;
+                    consecutive_errors = 0;
+                }
+                Err(err) => {
+                    consecutive_errors += 1;
+                    tracing::warn!("activity stream error ({consecutive_errors}): {err}");
+                    if consecutive_errors >= config.max_consecutive_errors {
+                        tracing::error!("activity stream giving up after repeated errors");
+                        return;
+                    }
+                }
+            }
+            if since.is_some() && !produced_output {
+                // bd rejected --since (or the resume point): stop trying to
+                // resume and ask for a reconcile instead, since events may
+                // have been missed.
+                since_supported = false;
+                tracing::warn!("bd does not support --since; requesting full refresh");
+                if let Some(notices) = &notices {
+                    let _ = notices
+                        .send(DashboardEvent::CacheRefreshed(
+                            "activity stream cannot resume; full refresh needed".to_string(),
+                        ))
+                        .await;
+                }
+            }
+            // Whether bd exited cleanly or the connection went bad, we are
+            // now disconnected until the next spawn reads a line.
+            if connected {
+                connected = false;
+                if let Some(notices) = &notices {
+                    let _ = notices
+                        .send(DashboardEvent::ConnectionChanged { connected: false })
+                        .await;
+                }
+            }
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(config.max_backoff);
+        }
+    }
+
+    /// One connection: spawn `bd activity --follow` and forward events until
+    /// the process exits or the parse-error cap trips.
+    async fn run_stream(
+        &self,
+        tx: &mpsc::Sender<ActivityEvent>,
+        notices: Option<&mpsc::Sender<DashboardEvent>>,
+        connected: &mut bool,
+        since: Option<&str>,
+        event_types: Option<&HashSet<String>>,
+    ) -> std::io::Result<()> {
+        let mut child = Command::new(&self.bd_path)
+            .args(Self::build_stream_args(since))
+            .current_dir(&self.workspace)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("stdout piped");
+        let mut lines = BufReader::new(stdout).lines();
+        let mut parser = LineParser::default();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            // First line off a fresh connection: we're demonstrably back.
+            if !*connected {
+                *connected = true;
+                if let Some(notices) = notices {
+                    let _ = notices
+                        .send(DashboardEvent::ConnectionChanged { connected: true })
+                        .await;
+                }
+            }
+            match parser.handle_line(&line) {
+                LineOutcome::Event(event) => {
+                    // The cursor advances even past filtered events so a
+                    // reconnect doesn't replay them.
+                    if let Some(ts) = &event.timestamp {
+                        *self.last_seen.lock().unwrap() = Some(ts.clone());
+                    }
+                    if event_types.is_some_and(|wanted| !wanted.contains(&event.event_type)) {
+                        continue;
+                    }
+                    if tx.send(event).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                LineOutcome::Skip => {}
+                LineOutcome::Warn(sample) => {
+                    tracing::warn!("activity stream: repeated parse errors, sample: {sample}");
+                    if let Some(notices) = notices {
+                        let _ = notices
+                            .send(DashboardEvent::Error(format!(
+                                "bd is emitting activity events this app can't parse \
+                                 (possible bd version mismatch). Sample: {sample}"
+                            )))
+                            .await;
+                    }
+                }
+                LineOutcome::RestartStream => {
+                    return Err(std::io::Error::other("too many parse errors"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The entity an event is about, for coalescing: the issue ID, or the gate
+/// ID in either of the shapes bd emits it. Events about neither (daemon
+/// notices and the like) return `None` and are never coalesced.
+fn entity_key(event: &ActivityEvent) -> Option<String> {
+    if let Some(id) = &event.issue_id {
+        return Some(format!("issue:{id}"));
+    }
+    event
+        .extra
+        .get("gate_id")
+        .and_then(Value::as_str)
+        .or_else(|| {
+            event
+                .extra
+                .get("gate")
+                .and_then(|g| g.get("id"))
+                .and_then(Value::as_str)
+        })
+        .map(|id| format!("gate:{id}"))
+}
+
+/// Collapse bursts of events: after the first event arrives, anything else
+/// received within `window` is batched, and only the latest event per
+/// entity survives. A bd reindex firing dozens of `issue.updated` for the
+/// same issue thus produces one cache write and one UI event. First-arrival
+/// order is preserved; events without an entity pass through unmerged.
+pub fn coalesce_events(
+    mut rx: mpsc::Receiver<ActivityEvent>,
+    window: Duration,
+) -> mpsc::Receiver<ActivityEvent> {
+    let (tx, out) = mpsc::channel(256);
+    tokio::spawn(async move {
+        while let Some(first) = rx.recv().await {
+            let mut batch: Vec<(Option<String>, ActivityEvent)> =
+                vec![(entity_key(&first), first)];
+            let deadline = tokio::time::Instant::now() + window;
+            loop {
+                let event = match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(Some(event)) => event,
+                    // Window elapsed or the stream ended: flush what we have.
+                    Ok(None) | Err(_) => break,
+                };
+                let key = entity_key(&event);
+                let existing = key
+                    .as_ref()
+                    .and_then(|k| batch.iter().position(|(bk, _)| bk.as_ref() == Some(k)));
+                match existing {
+                    Some(pos) => batch[pos] = (key, event),
+                    None => batch.push((key, event)),
+                }
+            }
+            for (_, event) in batch {
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warning_emitted_once_past_threshold() {
+        let mut parser = LineParser::default();
+        let mut warnings = 0;
+        for _ in 0..PARSE_ERROR_WARN_THRESHOLD + 5 {
+            match parser.handle_line("not json at all") {
+                LineOutcome::Warn(sample) => {
+                    warnings += 1;
+                    assert!(sample.contains("not json"));
+                }
+                LineOutcome::Skip => {}
+                other => panic!("unexpected outcome: {other:?}"),
+            }
+        }
+        assert_eq!(warnings, 1);
+    }
+
+    #[test]
+    fn interspersed_good_lines_do_not_reset_the_cap() {
+        let mut parser = LineParser::default();
+        let good = "{\"event_type\":\"issue.updated\",\"issue_id\":\"bd-1\"}";
+        let mut restarted_after = None;
+        for i in 0..MAX_PARSE_ERRORS * 3 {
+            let line = if i % 2 == 0 { "garbage" } else { good };
+            if matches!(parser.handle_line(line), LineOutcome::RestartStream) {
+                restarted_after = Some(i + 1);
+                break;
+            }
+        }
+        // Bad lines on even indices: the cap trips on the MAX'th of them,
+        // good lines notwithstanding.
+        assert_eq!(restarted_after, Some(MAX_PARSE_ERRORS * 2 - 1));
+    }
+
+    #[test]
+    fn hard_cap_still_requests_restart() {
+        let mut parser = LineParser::default();
+        let mut restarted = false;
+        for _ in 0..MAX_PARSE_ERRORS {
+            if matches!(parser.handle_line("garbage"), LineOutcome::RestartStream) {
+                restarted = true;
+                break;
+            }
+        }
+        assert!(restarted);
+    }
+
+    #[test]
+    fn stream_args_carry_since_only_when_resuming() {
+        assert_eq!(
+            ActivityStream::build_stream_args(None),
+            vec!["activity", "--follow", "--json"]
+        );
+        assert_eq!(
+            ActivityStream::build_stream_args(Some("2024-01-01T00:00:00Z")),
+            vec!["activity", "--follow", "--json", "--since", "2024-01-01T00:00:00Z"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn second_spawn_resumes_from_the_last_seen_timestamp() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let args_log = dir.path().join("args");
+        let script = dir.path().join("bd");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho \"$@\" >> {}\n\
+                 echo '{{\"event_type\":\"issue.updated\",\"issue_id\":\"bd-1\",\
+                 \"timestamp\":\"2024-03-01T12:00:00Z\"}}'\n",
+                args_log.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let stream = ActivityStream::new(&script, dir.path());
+        let mut events = stream.start(None, None, StreamConfig::default());
+        // Drain the event from the first connection and one from the second.
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("timed out waiting for event")
+                .expect("stream closed");
+        }
+
+        let log = std::fs::read_to_string(&args_log).unwrap();
+        let spawns: Vec<&str> = log.lines().collect();
+        assert!(spawns.len() >= 2);
+        assert_eq!(spawns[0], "activity --follow --json");
+        assert_eq!(
+            spawns[1],
+            "activity --follow --json --since 2024-03-01T12:00:00Z"
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_error_cap_stops_the_stream_after_the_second_failure() {
+        // A nonexistent binary makes every connection attempt fail to spawn.
+        let stream = ActivityStream::new("/nonexistent/bd", "/tmp");
+        let config = StreamConfig {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            max_consecutive_errors: 2,
+        };
+        let mut events = stream.start(None, None, config);
+
+        // The task gives up after the second failure and drops the sender.
+        let closed = tokio::time::timeout(Duration::from_secs(5), events.recv()).await;
+        assert!(closed.expect("stream did not give up in time").is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn filtered_event_types_never_reach_the_channel() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("bd");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             echo '{\"event_type\":\"daemon.heartbeat\"}'\n\
+             echo '{\"event_type\":\"issue.updated\",\"issue_id\":\"bd-1\"}'\n\
+             sleep 5\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let wanted: HashSet<String> = ["issue.updated".to_string()].into_iter().collect();
+        let stream = ActivityStream::new(&script, dir.path());
+        let mut events = stream.start(None, Some(wanted), StreamConfig::default());
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("timed out waiting for event")
+            .expect("stream closed");
+        // The heartbeat came first on the wire but was dropped.
+        assert_eq!(event.event_type, "issue.updated");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reconnect_fires_both_connection_transitions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("bd");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\necho '{\"event_type\":\"issue.updated\",\"issue_id\":\"bd-1\"}'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let (notices_tx, mut notices_rx) = mpsc::channel(16);
+        let stream = ActivityStream::new(&script, dir.path());
+        let _events = stream.start(Some(notices_tx), None, StreamConfig::default());
+
+        let mut transitions = Vec::new();
+        while transitions.len() < 3 {
+            let notice = tokio::time::timeout(Duration::from_secs(5), notices_rx.recv())
+                .await
+                .expect("timed out waiting for connection transition")
+                .expect("notices channel closed");
+            if let DashboardEvent::ConnectionChanged { connected } = notice {
+                transitions.push(connected);
+            }
+        }
+        // Connected on the first line, dropped when bd exits, connected
+        // again after the restart.
+        assert_eq!(transitions, vec![true, false, true]);
+    }
+
+    fn update(id: &str, title: &str) -> ActivityEvent {
+        serde_json::from_str(&format!(
+            "{{\"event_type\":\"issue.updated\",\"issue_id\":\"{id}\",\
+             \"issue\":{{\"id\":\"{id}\",\"title\":\"{title}\"}}}}"
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn burst_for_one_issue_coalesces_to_the_latest_event() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut out = coalesce_events(rx, COALESCE_WINDOW);
+
+        for title in ["first", "second", "third"] {
+            tx.send(update("bd-1", title)).await.unwrap();
+        }
+        drop(tx); // Closing the input flushes the window immediately.
+
+        let event = out.recv().await.expect("coalesced event");
+        assert_eq!(event.extra["issue"]["title"], "third");
+        assert!(out.recv().await.is_none(), "expected exactly one event");
+    }
+
+    #[tokio::test]
+    async fn distinct_entities_survive_in_arrival_order() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut out = coalesce_events(rx, COALESCE_WINDOW);
+
+        tx.send(update("bd-1", "a")).await.unwrap();
+        tx.send(update("bd-2", "b")).await.unwrap();
+        tx.send(update("bd-1", "c")).await.unwrap();
+        drop(tx);
+
+        let first = out.recv().await.unwrap();
+        let second = out.recv().await.unwrap();
+        assert_eq!(first.issue_id.as_deref(), Some("bd-1"));
+        assert_eq!(first.extra["issue"]["title"], "c");
+        assert_eq!(second.issue_id.as_deref(), Some("bd-2"));
+        assert!(out.recv().await.is_none());
+    }
+
+    #[test]
+    fn warning_sample_is_truncated() {
+        let long = "x".repeat(500);
+        assert!(truncate_sample(&long).chars().count() <= PARSE_ERROR_SAMPLE_LEN + 1);
+    }
+}
@@ -0,0 +1,1431 @@
+//! In-memory domain cache over bd data, persisted between sessions.
+//!
+//! The cache is the read path for most commands: it's populated by
+//! `full_refresh` and kept current by applying activity-stream events.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::activity::ActivityEvent;
+use super::status::{status_is_closed, IssueStatus};
+use super::types::{EpicStatus, Gate, Issue};
+
+/// Default staleness threshold; tunable per cache via
+/// [`BeadsCache::with_stale_after`].
+pub const STALE_DURATION: Duration = Duration::from_secs(30);
+
+const CACHE_FILE: &str = "agent-maestro-cache.json.gz";
+
+/// Pre-compression cache filename, still read (and cleaned up) for caches
+/// written by older builds.
+const LEGACY_CACHE_FILE: &str = "agent-maestro-cache.json";
+
+/// Leading bytes of every gzip stream, used to sniff legacy plain-JSON
+/// files on load.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Closed issues whose `closed_at` is older than this are dropped when the
+/// cache is compacted.
+const CLOSED_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 30);
+
+/// Hook invoked with an issue ID when an activity event references an issue
+/// without embedding its payload. Implementations are expected to fetch the
+/// issue out-of-band (e.g. spawn a task that calls `BdClient::get_issue` and
+/// upserts the result); `apply_event` never waits on it, so a failed fetch
+/// just means the issue stays stale until the next full refresh.
+pub type MissingIssueFetcher = Arc<dyn Fn(&str) + Send + Sync>;
+
+pub struct BeadsCache {
+    issues: HashMap<String, Issue>,
+    gates: HashMap<String, Gate>,
+    epics: HashMap<String, EpicStatus>,
+    last_full_sync: Option<Instant>,
+    /// Timestamp of the last activity event applied, so the stream can
+    /// resume from where the previous session left off.
+    last_event_ts: Option<String>,
+    /// Duplicate-ID collisions detected during the last `full_refresh`.
+    last_refresh_collisions: usize,
+    /// How long after a full sync this cache reports itself stale.
+    stale_after: Duration,
+    missing_issue_fetcher: Option<MissingIssueFetcher>,
+    /// Optional bound on cached issues; `None` means unbounded.
+    max_issues: Option<usize>,
+    /// Per-issue recency, behind a mutex so `get_issue` can touch it
+    /// through `&self`.
+    access_log: std::sync::Mutex<AccessLog>,
+}
+
+/// Logical-clock recency tracking for LRU eviction. A counter instead of
+/// `Instant` so two touches can never tie and eviction order stays
+/// deterministic.
+#[derive(Debug, Default)]
+struct AccessLog {
+    ticks: HashMap<String, u64>,
+    next: u64,
+}
+
+impl AccessLog {
+    fn touch(&mut self, id: &str) {
+        self.next += 1;
+        self.ticks.insert(id.to_string(), self.next);
+    }
+}
+
+impl std::fmt::Debug for BeadsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BeadsCache")
+            .field("issues", &self.issues)
+            .field("gates", &self.gates)
+            .field("epics", &self.epics)
+            .field("last_full_sync", &self.last_full_sync)
+            .field("last_event_ts", &self.last_event_ts)
+            .field("last_refresh_collisions", &self.last_refresh_collisions)
+            .field("stale_after", &self.stale_after)
+            .field(
+                "missing_issue_fetcher",
+                &self.missing_issue_fetcher.as_ref().map(|_| "<fetcher>"),
+            )
+            .field("max_issues", &self.max_issues)
+            .finish()
+    }
+}
+
+impl Default for BeadsCache {
+    fn default() -> Self {
+        Self {
+            issues: HashMap::new(),
+            gates: HashMap::new(),
+            epics: HashMap::new(),
+            last_full_sync: None,
+            last_event_ts: None,
+            last_refresh_collisions: 0,
+            stale_after: STALE_DURATION,
+            missing_issue_fetcher: None,
+            max_issues: None,
+            access_log: std::sync::Mutex::new(AccessLog::default()),
+        }
+    }
+}
+
+/// Sort keys accepted by [`BeadsCache::list_issues_sorted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Id,
+    Title,
+    Status,
+    Priority,
+    Assignee,
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "id" => Ok(Self::Id),
+            "title" => Ok(Self::Title),
+            "status" => Ok(Self::Status),
+            "priority" => Ok(Self::Priority),
+            "assignee" => Ok(Self::Assignee),
+            other => Err(format!("unknown sort key: {other}")),
+        }
+    }
+}
+
+/// Snapshot counts returned to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub total_issues: usize,
+    pub open: usize,
+    pub in_progress: usize,
+    pub blocked: usize,
+    pub closed: usize,
+    pub gates: usize,
+    pub pending_gates: usize,
+    pub epics: usize,
+    /// Seconds since the last full sync, if one has happened.
+    pub last_sync: Option<u64>,
+    /// Duplicate issue IDs seen in the last full refresh; non-zero signals
+    /// data corruption upstream.
+    pub id_collisions: usize,
+}
+
+/// On-disk representation of the cache.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SerializedCache {
+    pub issues: Vec<Issue>,
+    pub gates: Vec<Gate>,
+    pub epics: Vec<EpicStatus>,
+    pub last_full_sync: Option<String>,
+    /// Absent in cache files written before stream resume existed.
+    #[serde(default)]
+    pub last_event_ts: Option<String>,
+}
+
+/// Lightweight sync-age snapshot for the frequently polled
+/// `get_cache_age_secs` command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheAge {
+    /// Seconds since the last full sync; `None` before the first sync.
+    pub age_secs: Option<u64>,
+    pub is_stale: bool,
+}
+
+/// Estimated-effort rollup for one epic. See [`BeadsCache::epic_effort`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EpicEffort {
+    pub total: f64,
+    pub remaining: f64,
+    /// Issues that actually carried an estimate.
+    pub with_estimates: usize,
+    pub issue_count: usize,
+}
+
+/// Per-assignee open-work counts. See [`BeadsCache::workload`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkloadStats {
+    pub open: usize,
+    pub in_progress: usize,
+    /// Everything not closed, including blocked and unknown statuses.
+    pub total: usize,
+}
+
+/// Result of a [`BeadsCache::compact_cache`] run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub issues_before: usize,
+    pub issues_after: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl BeadsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache with a non-default staleness threshold.
+    pub fn with_stale_after(stale_after: Duration) -> Self {
+        Self {
+            stale_after,
+            ..Self::default()
+        }
+    }
+
+    pub fn stale_after(&self) -> Duration {
+        self.stale_after
+    }
+
+    /// Install the fallback used when an event arrives without its payload.
+    /// Wired together with the activity stream; before that, payload-less
+    /// events just log.
+    pub fn set_missing_issue_fetcher(&mut self, fetcher: MissingIssueFetcher) {
+        self.missing_issue_fetcher = Some(fetcher);
+    }
+
+    /// Adjust the staleness threshold at runtime.
+    pub fn set_stale_after(&mut self, stale_after: Duration) {
+        self.stale_after = stale_after;
+    }
+
+    /// A cache that evicts least-recently-accessed issues past `max_issues`.
+    /// Evicted issues are transparently re-fetched from bd the next time a
+    /// command asks for them.
+    pub fn with_max_issues(max_issues: usize) -> Self {
+        Self {
+            max_issues: Some(max_issues),
+            ..Self::default()
+        }
+    }
+
+    /// Change (or remove) the issue cap at runtime; a tighter cap takes
+    /// effect on the next insert.
+    pub fn set_max_issues(&mut self, max_issues: Option<usize>) {
+        self.max_issues = max_issues;
+    }
+
+    /// Drop least-recently-accessed issues until the cap is respected.
+    /// Issues never touched rank oldest.
+    fn enforce_issue_cap(&mut self) {
+        let Some(cap) = self.max_issues else {
+            return;
+        };
+        if self.issues.len() <= cap {
+            return;
+        }
+        let mut log = self.access_log.lock().unwrap();
+        let mut by_recency: Vec<(u64, String)> = self
+            .issues
+            .keys()
+            .map(|id| (log.ticks.get(id).copied().unwrap_or(0), id.clone()))
+            .collect();
+        by_recency.sort_unstable();
+        let excess = self.issues.len() - cap;
+        for (_, id) in by_recency.into_iter().take(excess) {
+            self.issues.remove(&id);
+            log.ticks.remove(&id);
+        }
+    }
+
+    /// Default directory for the persisted cache
+    /// (`dirs::cache_dir()/agent-maestro`); `None` when the platform has
+    /// no cache dir.
+    pub fn default_cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("agent-maestro"))
+    }
+
+    /// Default cache file location inside `dir`.
+    pub fn cache_file_path(dir: &Path) -> PathBuf {
+        dir.join(CACHE_FILE)
+    }
+
+    /// Where an older build would have written the uncompressed cache.
+    pub fn legacy_cache_file_path(dir: &Path) -> PathBuf {
+        dir.join(LEGACY_CACHE_FILE)
+    }
+
+    /// Replace the cache contents wholesale from a fresh bd read. Returns
+    /// the number of duplicate-ID collisions found in `issues`; on a
+    /// collision the issue with the newer `updated_at` wins, deterministic
+    /// regardless of bd's output order.
+    pub fn full_refresh(
+        &mut self,
+        issues: Vec<Issue>,
+        gates: Vec<Gate>,
+        epics: Vec<EpicStatus>,
+    ) -> usize {
+        use std::collections::hash_map::Entry;
+
+        let mut map: HashMap<String, Issue> = HashMap::with_capacity(issues.len());
+        let mut duplicates = Vec::new();
+        let mut log = self.access_log.lock().unwrap();
+        log.ticks.clear();
+        for issue in issues {
+            log.touch(&issue.id);
+            match map.entry(issue.id.clone()) {
+                Entry::Vacant(slot) => {
+                    slot.insert(issue);
+                }
+                Entry::Occupied(mut slot) => {
+                    duplicates.push(issue.id.clone());
+                    // Keep the existing one unless the incoming copy is
+                    // strictly newer.
+                    if issue.updated_at_ts() > slot.get().updated_at_ts() {
+                        slot.insert(issue);
+                    }
+                }
+            }
+        }
+        drop(log);
+        if !duplicates.is_empty() {
+            tracing::warn!(
+                "bd returned duplicate issue IDs: {}",
+                duplicates.join(", ")
+            );
+        }
+
+        self.issues = map;
+        self.gates = gates.into_iter().map(|g| (g.id.clone(), g)).collect();
+        self.epics = epics.into_iter().map(|e| (e.epic_id.clone(), e)).collect();
+        self.last_full_sync = Some(Instant::now());
+        self.last_refresh_collisions = duplicates.len();
+        self.enforce_issue_cap();
+        duplicates.len()
+    }
+
+    /// Apply one activity-stream event incrementally.
+    pub fn apply_event(&mut self, event: &ActivityEvent) {
+        if let Some(ts) = &event.timestamp {
+            self.last_event_ts = Some(ts.clone());
+        }
+        match event.event_type.as_str() {
+            "issue.created" | "issue.updated" => {
+                if let Some(issue) = deserialize_extra::<Issue>(event, "issue") {
+                    self.access_log.lock().unwrap().touch(&issue.id);
+                    self.issues.insert(issue.id.clone(), issue);
+                    self.enforce_issue_cap();
+                } else if let Some(id) = &event.issue_id {
+                    // Payload-less event; hand the ID to the fetcher rather
+                    // than dropping the update on the floor.
+                    match &self.missing_issue_fetcher {
+                        Some(fetch) => {
+                            tracing::debug!("event for {id} carried no payload, fetching");
+                            fetch(id);
+                        }
+                        None => {
+                            tracing::warn!(
+                                "event for {id} carried no payload and no fetcher is configured"
+                            );
+                        }
+                    }
+                }
+            }
+            "issue.deleted" => {
+                if let Some(id) = &event.issue_id {
+                    self.issues.remove(id);
+                }
+            }
+            "gate.created" | "gate.updated" | "gate.resolved" => {
+                if let Some(gate) = deserialize_extra::<Gate>(event, "gate") {
+                    self.gates.insert(gate.id.clone(), gate);
+                }
+            }
+            "gate.deleted" => {
+                if let Some(id) = event.extra.get("gate_id").and_then(|v| v.as_str()) {
+                    self.gates.remove(id);
+                }
+            }
+            "epic.created" | "epic.updated" => {
+                if let Some(epic) = deserialize_extra::<EpicStatus>(event, "epic") {
+                    self.epics.insert(epic.epic_id.clone(), epic);
+                }
+            }
+            "epic.deleted" => {
+                if let Some(id) = event.extra.get("epic_id").and_then(|v| v.as_str()) {
+                    self.epics.remove(id);
+                }
+            }
+            other => {
+                tracing::warn!("Unknown event type: {other}");
+            }
+        }
+    }
+
+    pub fn get_issue(&self, id: &str) -> Option<&Issue> {
+        let issue = self.issues.get(id);
+        if issue.is_some() {
+            self.access_log.lock().unwrap().touch(id);
+        }
+        issue
+    }
+
+    /// Insert or replace an issue immediately (e.g. right after a write
+    /// returned the updated copy) so reads like `get_dag` see it without
+    /// waiting for the next activity event.
+    pub fn upsert_issue(&mut self, issue: Issue) {
+        self.access_log.lock().unwrap().touch(&issue.id);
+        self.issues.insert(issue.id.clone(), issue);
+        self.enforce_issue_cap();
+    }
+
+    /// Drop an issue immediately (e.g. right after a delete) so the UI
+    /// doesn't wait for the next activity event.
+    pub fn remove_issue(&mut self, id: &str) -> Option<Issue> {
+        self.issues.remove(id)
+    }
+
+    pub fn list_issues(&self) -> Vec<Issue> {
+        self.issues.values().cloned().collect()
+    }
+
+    pub fn issues_map(&self) -> &HashMap<String, Issue> {
+        &self.issues
+    }
+
+    /// Insert or replace a gate immediately (e.g. right after a resolve
+    /// returned the updated copy) so pending counts don't wait for the
+    /// next activity event.
+    pub fn upsert_gate(&mut self, gate: Gate) {
+        self.gates.insert(gate.id.clone(), gate);
+    }
+
+    pub fn gates(&self) -> Vec<Gate> {
+        self.gates.values().cloned().collect()
+    }
+
+    pub fn get_pending_gates(&self) -> Vec<Gate> {
+        self.gates
+            .values()
+            .filter(|g| g.status == "pending")
+            .cloned()
+            .collect()
+    }
+
+    /// Open issues with no unresolved blocking dependency.
+    pub fn list_ready(&self) -> Vec<Issue> {
+        self.issues
+            .values()
+            .filter(|issue| {
+                !status_is_closed(&issue.status)
+                    && issue.dependency_ids().iter().all(|dep| {
+                        super::dag::resolve_issue_id(dep, &self.issues)
+                            .and_then(|id| self.issues.get(&id))
+                            .map(|d| status_is_closed(&d.status))
+                            .unwrap_or(false)
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The complement of [`BeadsCache::list_ready`] for the dashboard's
+    /// blocked view: open issues whose status normalizes to blocked, or
+    /// with at least one dependency that isn't closed (including
+    /// dependencies that can't be resolved at all).
+    pub fn list_blocked(&self) -> Vec<Issue> {
+        self.issues
+            .values()
+            .filter(|issue| {
+                if status_is_closed(&issue.status) {
+                    return false;
+                }
+                issue.status_category() == IssueStatus::Blocked
+                    || issue.dependency_ids().iter().any(|dep| {
+                        !super::dag::resolve_issue_id(dep, &self.issues)
+                            .and_then(|id| self.issues.get(&id))
+                            .map(|d| status_is_closed(&d.status))
+                            .unwrap_or(false)
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Case-insensitive substring search over title and status.
+    pub fn search_issues(&self, query: &str) -> Vec<Issue> {
+        let query = query.to_lowercase();
+        self.issues
+            .values()
+            .filter(|issue| {
+                issue.title.to_lowercase().contains(&query)
+                    || issue.status.to_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Issues sorted in the cache instead of client-side. `desc` reverses
+    /// the order; missing values (priority, assignee) always sort last
+    /// either way so real data stays at the top of the list.
+    pub fn list_issues_sorted(&self, by: SortKey, desc: bool) -> Vec<Issue> {
+        let mut issues = self.list_issues();
+        match by {
+            SortKey::Id => issues.sort_by(|a, b| a.id.cmp(&b.id)),
+            SortKey::Title => issues.sort_by(|a, b| a.title.cmp(&b.title)),
+            SortKey::Status => issues.sort_by(|a, b| a.status.cmp(&b.status)),
+            SortKey::Priority => issues.sort_by_key(super::recommend::priority_rank),
+            SortKey::Assignee => issues.sort_by(|a, b| {
+                match (a.effective_assignee(), b.effective_assignee()) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                }
+            }),
+        }
+        if desc {
+            // Keep the "missing sorts last" invariant by only reversing the
+            // issues that actually carry a value for the key.
+            let split = issues
+                .iter()
+                .position(|issue| match by {
+                    SortKey::Priority => super::recommend::priority_rank(issue) == i64::MAX,
+                    SortKey::Assignee => issue.effective_assignee().is_none(),
+                    _ => false,
+                })
+                .unwrap_or(issues.len());
+            issues[..split].reverse();
+        }
+        issues
+    }
+
+    /// [`BeadsCache::search_issues`] plus exact filters, all ANDed: every
+    /// requested label must be on the issue, and status/assignee must match
+    /// exactly (case-insensitively). An empty `query` matches everything.
+    pub fn search_issues_advanced(
+        &self,
+        query: &str,
+        labels: &[String],
+        status: Option<&str>,
+        assignee: Option<&str>,
+    ) -> Vec<Issue> {
+        let query = query.to_lowercase();
+        self.issues
+            .values()
+            .filter(|issue| {
+                if !query.is_empty()
+                    && !issue.title.to_lowercase().contains(&query)
+                    && !issue.status.to_lowercase().contains(&query)
+                {
+                    return false;
+                }
+                if let Some(status) = status {
+                    if !issue.status.eq_ignore_ascii_case(status) {
+                        return false;
+                    }
+                }
+                if let Some(assignee) = assignee {
+                    let matches = issue
+                        .effective_assignee()
+                        .is_some_and(|a| a.eq_ignore_ascii_case(assignee));
+                    if !matches {
+                        return false;
+                    }
+                }
+                labels.iter().all(|wanted| {
+                    issue
+                        .labels
+                        .iter()
+                        .any(|label| label.eq_ignore_ascii_case(wanted))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Open issues whose last recorded activity is older than `older_than`.
+    /// Issues that carry no activity timestamp at all can't be aged and are
+    /// left out; a debug log reports how many were skipped that way.
+    pub fn stale_issues(&self, older_than: Duration) -> Vec<Issue> {
+        let now = chrono::Utc::now();
+        let threshold = chrono::Duration::from_std(older_than)
+            .unwrap_or_else(|_| chrono::Duration::max_value());
+        let mut missing_timestamp = 0usize;
+        let mut stale = Vec::new();
+        for issue in self.issues.values() {
+            if status_is_closed(&issue.status) {
+                continue;
+            }
+            match issue.last_activity_ts() {
+                Some(ts) if now.signed_duration_since(ts) > threshold => {
+                    stale.push(issue.clone());
+                }
+                Some(_) => {}
+                None => missing_timestamp += 1,
+            }
+        }
+        if missing_timestamp > 0 {
+            tracing::debug!(
+                "{missing_timestamp} open issue(s) have no activity timestamp; \
+                 excluded from staleness"
+            );
+        }
+        stale
+    }
+
+    /// Open work bucketed by [`Issue::effective_assignee`]; issues without
+    /// one land under `"unassigned"`. Closed issues don't count at all, so
+    /// an assignee with only closed work simply has no entry.
+    pub fn workload(&self) -> HashMap<String, WorkloadStats> {
+        let mut buckets: HashMap<String, WorkloadStats> = HashMap::new();
+        for issue in self.issues.values() {
+            let category = issue.status_category();
+            if category == IssueStatus::Closed {
+                continue;
+            }
+            let assignee = issue
+                .effective_assignee()
+                .unwrap_or("unassigned")
+                .to_string();
+            let stats = buckets.entry(assignee).or_default();
+            stats.total += 1;
+            match category {
+                IssueStatus::Open | IssueStatus::Unknown => stats.open += 1,
+                IssueStatus::InProgress => stats.in_progress += 1,
+                IssueStatus::Blocked | IssueStatus::Closed => {}
+            }
+        }
+        buckets
+    }
+
+    pub fn get_epic_status(&self, epic_id: &str) -> Option<&EpicStatus> {
+        self.epics.get(epic_id)
+    }
+
+    pub fn list_epics(&self) -> Vec<EpicStatus> {
+        self.epics.values().cloned().collect()
+    }
+
+    /// Seconds since the last full sync; `None` before the first sync.
+    pub fn age_secs(&self) -> Option<u64> {
+        self.last_full_sync.map(|at| at.elapsed().as_secs())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_last_sync_for_tests(&mut self, at: Instant) {
+        self.last_full_sync = Some(at);
+    }
+
+    /// Timestamp of the last activity event applied, surviving restarts via
+    /// the serialized cache; the stream passes it to `--since` on resume.
+    pub fn last_event_ts(&self) -> Option<&str> {
+        self.last_event_ts.as_deref()
+    }
+
+    pub fn is_stale(&self) -> bool {
+        match self.last_full_sync {
+            Some(at) => at.elapsed() > self.stale_after,
+            None => true,
+        }
+    }
+
+    pub fn get_stats(&self) -> CacheStats {
+        let mut open = 0;
+        let mut in_progress = 0;
+        let mut blocked = 0;
+        let mut closed = 0;
+        for issue in self.issues.values() {
+            match issue.status_category() {
+                IssueStatus::InProgress => in_progress += 1,
+                IssueStatus::Blocked => blocked += 1,
+                IssueStatus::Closed => closed += 1,
+                IssueStatus::Open | IssueStatus::Unknown => open += 1,
+            }
+        }
+        CacheStats {
+            total_issues: self.issues.len(),
+            open,
+            in_progress,
+            blocked,
+            closed,
+            gates: self.gates.len(),
+            pending_gates: self.get_pending_gates().len(),
+            epics: self.epics.len(),
+            last_sync: self.last_full_sync.map(|at| at.elapsed().as_secs()),
+            id_collisions: self.last_refresh_collisions,
+        }
+    }
+
+    /// Estimated-effort rollup across an epic's issues. Issues without an
+    /// estimate contribute zero; `with_estimates` reports coverage so the UI
+    /// can qualify the numbers.
+    pub fn epic_effort(&self, epic_id: &str) -> EpicEffort {
+        let mut effort = EpicEffort::default();
+        for issue in self
+            .issues
+            .values()
+            .filter(|issue| super::dag::is_issue_in_epic(issue, epic_id))
+        {
+            effort.issue_count += 1;
+            let Some(estimate) = issue.estimate() else {
+                continue;
+            };
+            effort.with_estimates += 1;
+            effort.total += estimate;
+            if !status_is_closed(&issue.status) {
+                effort.remaining += estimate;
+            }
+        }
+        effort
+    }
+
+    /// Recompute every epic rollup from the issues currently in the cache.
+    fn recompute_epic_rollups(&mut self) {
+        for epic in self.epics.values_mut() {
+            let members: Vec<&Issue> = self
+                .issues
+                .values()
+                .filter(|issue| super::dag::is_issue_in_epic(issue, &epic.epic_id))
+                .collect();
+            epic.total_issues = members.len();
+            epic.closed_issues = members
+                .iter()
+                .filter(|i| status_is_closed(&i.status))
+                .count();
+            epic.in_progress = members
+                .iter()
+                .filter(|i| i.status_category() == IssueStatus::InProgress)
+                .count();
+            epic.blocked = members
+                .iter()
+                .filter(|i| i.status_category() == IssueStatus::Blocked)
+                .count();
+        }
+    }
+
+    fn to_serialized(&self) -> SerializedCache {
+        SerializedCache {
+            issues: self.issues.values().cloned().collect(),
+            gates: self.gates.values().cloned().collect(),
+            epics: self.epics.values().cloned().collect(),
+            last_full_sync: self.last_full_sync.map(|at| {
+                format!("{}s ago at save", at.elapsed().as_secs())
+            }),
+            last_event_ts: self.last_event_ts.clone(),
+        }
+    }
+
+    fn load_serialized(&mut self, data: SerializedCache) {
+        self.issues = data.issues.into_iter().map(|i| (i.id.clone(), i)).collect();
+        self.gates = data.gates.into_iter().map(|g| (g.id.clone(), g)).collect();
+        self.epics = data
+            .epics
+            .into_iter()
+            .map(|e| (e.epic_id.clone(), e))
+            .collect();
+        // A loaded cache is always considered stale until the next sync.
+        self.last_full_sync = None;
+        self.last_event_ts = data.last_event_ts;
+    }
+
+    /// Persist atomically: write a temp file in the same directory and
+    /// rename it over the target, so a crash mid-write can't leave a
+    /// truncated cache. The payload is gzipped; the previous good copy is
+    /// kept as `.bak` for the corrupt-load fallback. After a successful
+    /// write, an uncompressed file left behind by an older build is deleted.
+    pub fn save_to_disk(&self, path: &Path) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let json = serde_json::to_vec(&self.to_serialized())?;
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&json)?;
+        let compressed = encoder.finish()?;
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, &compressed)?;
+        if path.exists() {
+            // Best-effort; losing the backup only costs the fallback.
+            if let Err(err) = std::fs::copy(path, path.with_extension("bak")) {
+                tracing::warn!("failed to keep cache backup: {err}");
+            }
+        }
+        std::fs::rename(&tmp, path)?;
+
+        // One-time migration: the legacy uncompressed file is now shadowed.
+        if path.file_name().and_then(|n| n.to_str()) == Some(CACHE_FILE) {
+            if let Some(dir) = path.parent() {
+                let legacy = Self::legacy_cache_file_path(dir);
+                if legacy.exists() {
+                    if let Err(err) = std::fs::remove_file(&legacy) {
+                        tracing::warn!("failed to remove legacy cache file: {err}");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the cache file, transparently decompressing gzip and accepting
+    /// legacy plain JSON. Falls back to the `.bak` copy, then to the
+    /// uncompressed legacy file, when the main file is missing or corrupt.
+    pub fn load_from_disk(&mut self, path: &Path) -> std::io::Result<()> {
+        let data = match Self::read_serialized(path) {
+            Ok(data) => data,
+            Err(err) => {
+                let bak = path.with_extension("bak");
+                tracing::warn!(
+                    "cache file unreadable ({err}); trying backup {}",
+                    bak.display()
+                );
+                match Self::read_serialized(&bak) {
+                    Ok(data) => data,
+                    Err(_) => {
+                        let legacy = path
+                            .parent()
+                            .map(Self::legacy_cache_file_path)
+                            .unwrap_or_default();
+                        Self::read_serialized(&legacy)?
+                    }
+                }
+            }
+        };
+        self.load_serialized(data);
+        Ok(())
+    }
+
+    fn read_serialized(path: &Path) -> std::io::Result<SerializedCache> {
+        use std::io::Read;
+
+        let bytes = std::fs::read(path)?;
+        let json = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut decoder = flate2::read::GzDecoder::new(bytes.as_slice());
+            let mut json = Vec::new();
+            decoder.read_to_end(&mut json)?;
+            json
+        } else {
+            bytes
+        };
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// Maintenance: drop closed issues past the retention window, recompute
+    /// epic rollups from what remains, and rewrite the cache file with a
+    /// single clean write (temp file + rename).
+    pub fn compact_cache(&mut self, path: &Path) -> std::io::Result<CompactionReport> {
+        let bytes_before = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let issues_before = self.issues.len();
+
+        self.issues.retain(|_, issue| {
+            if !status_is_closed(&issue.status) {
+                return true;
+            }
+            match issue.closed_at_ts() {
+                // Closed without a parseable timestamp: keep, to be safe.
+                None => true,
+                Some(closed_at) => {
+                    let age = chrono::Utc::now().signed_duration_since(closed_at);
+                    age.to_std().map(|age| age < CLOSED_RETENTION).unwrap_or(true)
+                }
+            }
+        });
+        self.recompute_epic_rollups();
+
+        self.save_to_disk(path)?;
+        let bytes_after = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(CompactionReport {
+            issues_before,
+            issues_after: self.issues.len(),
+            bytes_before,
+            bytes_after,
+        })
+    }
+}
+
+fn deserialize_extra<T: serde::de::DeserializeOwned>(
+    event: &ActivityEvent,
+    key: &str,
+) -> Option<T> {
+    event
+        .extra
+        .get(key)
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(value: serde_json::Value) -> Issue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn epic(id: &str) -> EpicStatus {
+        serde_json::from_value(json!({ "epic_id": id, "title": id })).unwrap()
+    }
+
+    #[test]
+    fn list_blocked_catches_both_flavors_of_blockage() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "status blocked", "status": "blocked"})),
+                issue(json!({
+                    "id": "bd-2", "title": "dep blocked", "status": "open",
+                    "dependencies": ["bd-4"]
+                })),
+                issue(json!({
+                    "id": "bd-3", "title": "ready", "status": "open",
+                    "dependencies": ["bd-5"]
+                })),
+                issue(json!({"id": "bd-4", "title": "blocker", "status": "open"})),
+                issue(json!({"id": "bd-5", "title": "finished", "status": "closed"})),
+            ],
+            vec![],
+            vec![],
+        );
+
+        let mut blocked: Vec<String> =
+            cache.list_blocked().into_iter().map(|i| i.id).collect();
+        blocked.sort();
+        // bd-4 is open with no dependencies, so it's ready, not blocked.
+        assert_eq!(blocked, ["bd-1", "bd-2"]);
+    }
+
+    #[test]
+    fn stale_issues_split_on_the_age_threshold() {
+        let ts = |days: i64| (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "old", "status": "open",
+                             "updated_at": ts(10)})),
+                issue(json!({"id": "bd-2", "title": "fresh", "status": "open",
+                             "updated_at": ts(1)})),
+                // Legacy field name still ages the issue.
+                issue(json!({"id": "bd-3", "title": "legacy", "status": "open",
+                             "last_activity": ts(20)})),
+                issue(json!({"id": "bd-4", "title": "undated", "status": "open"})),
+                issue(json!({"id": "bd-5", "title": "done", "status": "closed",
+                             "updated_at": ts(30)})),
+            ],
+            vec![],
+            vec![],
+        );
+
+        let mut stale: Vec<String> = cache
+            .stale_issues(Duration::from_secs(7 * 24 * 60 * 60))
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        stale.sort();
+        assert_eq!(stale, ["bd-1", "bd-3"]);
+    }
+
+    #[test]
+    fn workload_buckets_by_assignee_with_an_unassigned_catchall() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "a", "status": "open", "assignee": "alice"})),
+                issue(json!({"id": "bd-2", "title": "b", "status": "doing", "assignee": "alice"})),
+                // Legacy owner field counts via effective_assignee.
+                issue(json!({"id": "bd-3", "title": "c", "status": "open", "owner": "bob"})),
+                issue(json!({"id": "bd-4", "title": "d", "status": "open"})),
+                issue(json!({"id": "bd-5", "title": "e", "status": "blocked"})),
+                issue(json!({"id": "bd-6", "title": "f", "status": "closed", "assignee": "carol"})),
+            ],
+            vec![],
+            vec![],
+        );
+
+        let workload = cache.workload();
+        let alice = &workload["alice"];
+        assert_eq!((alice.open, alice.in_progress, alice.total), (1, 1, 2));
+        assert_eq!(workload["bob"].open, 1);
+        let unassigned = &workload["unassigned"];
+        assert_eq!(unassigned.open, 1);
+        assert_eq!(unassigned.total, 2, "blocked work still counts as load");
+        assert!(
+            !workload.contains_key("carol"),
+            "only closed work means no entry"
+        );
+    }
+
+    #[test]
+    fn compaction_prunes_old_closed_and_recomputes_rollups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILE);
+
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-e.1", "title": "open one", "status": "open"})),
+                issue(json!({
+                    "id": "bd-e.2",
+                    "title": "ancient",
+                    "status": "closed",
+                    "closed_at": "2020-01-01T00:00:00Z"
+                })),
+                issue(json!({
+                    "id": "bd-e.3",
+                    "title": "recent",
+                    "status": "closed",
+                    "closed_at": chrono::Utc::now().to_rfc3339()
+                })),
+            ],
+            vec![],
+            vec![epic("bd-e")],
+        );
+        cache.save_to_disk(&path).unwrap();
+        let before = std::fs::metadata(&path).unwrap().len();
+
+        let report = cache.compact_cache(&path).unwrap();
+
+        assert_eq!(report.issues_before, 3);
+        assert_eq!(report.issues_after, 2);
+        assert_eq!(report.bytes_before, before);
+        assert!(report.bytes_after < report.bytes_before);
+        assert!(std::fs::metadata(&path).unwrap().len() < before);
+
+        let epic = cache.get_epic_status("bd-e").unwrap();
+        assert_eq!(epic.total_issues, 2);
+        assert_eq!(epic.closed_issues, 1);
+    }
+
+    #[test]
+    fn cache_age_tracks_last_sync() {
+        let mut cache = BeadsCache::new();
+        assert_eq!(cache.age_secs(), None);
+        assert!(cache.is_stale());
+
+        cache.full_refresh(vec![], vec![], vec![]);
+        assert_eq!(cache.age_secs(), Some(0));
+        assert!(!cache.is_stale());
+
+        cache.set_last_sync_for_tests(Instant::now() - Duration::from_secs(42));
+        assert_eq!(cache.age_secs(), Some(42));
+        assert!(cache.is_stale());
+    }
+
+    #[test]
+    fn staleness_threshold_is_configurable() {
+        let mut cache = BeadsCache::with_stale_after(Duration::from_secs(120));
+        cache.full_refresh(vec![], vec![], vec![]);
+        cache.set_last_sync_for_tests(Instant::now() - Duration::from_secs(42));
+        assert!(!cache.is_stale());
+
+        cache.set_stale_after(Duration::from_secs(10));
+        assert!(cache.is_stale());
+    }
+
+    #[test]
+    fn epic_effort_rolls_up_and_reports_coverage() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-e.1", "title": "a", "status": "open", "estimate": 3})),
+                issue(json!({"id": "bd-e.2", "title": "b", "status": "closed", "estimate": "2d"})),
+                issue(json!({"id": "bd-e.3", "title": "c", "status": "open"})),
+            ],
+            vec![],
+            vec![epic("bd-e")],
+        );
+        let effort = cache.epic_effort("bd-e");
+        assert_eq!(effort.issue_count, 3);
+        assert_eq!(effort.with_estimates, 2);
+        assert_eq!(effort.total, 5.0);
+        assert_eq!(effort.remaining, 3.0);
+    }
+
+    #[test]
+    fn duplicate_ids_keep_the_newer_copy_and_are_counted() {
+        let older = issue(json!({
+            "id": "bd-1", "title": "older", "status": "open",
+            "updated_at": "2024-01-01T00:00:00Z"
+        }));
+        let newer = issue(json!({
+            "id": "bd-1", "title": "newer", "status": "open",
+            "updated_at": "2024-06-01T00:00:00Z"
+        }));
+
+        let mut cache = BeadsCache::new();
+        let collisions = cache.full_refresh(vec![older.clone(), newer.clone()], vec![], vec![]);
+        assert_eq!(collisions, 1);
+        assert_eq!(cache.get_issue("bd-1").unwrap().title, "newer");
+        assert_eq!(cache.get_stats().id_collisions, 1);
+
+        // Same outcome with the order reversed.
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(vec![newer, older], vec![], vec![]);
+        assert_eq!(cache.get_issue("bd-1").unwrap().title, "newer");
+    }
+
+    #[test]
+    fn apply_event_inserts_embedded_issue() {
+        let mut cache = BeadsCache::new();
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "issue.created",
+            "issue_id": "bd-1",
+            "issue": {"id": "bd-1", "title": "new", "status": "open"}
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+        assert!(cache.get_issue("bd-1").is_some());
+    }
+
+    fn created_event(id: &str) -> ActivityEvent {
+        serde_json::from_value(json!({
+            "event_type": "issue.created",
+            "issue_id": id,
+            "issue": {"id": id, "title": id, "status": "open"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn inserting_beyond_the_cap_evicts_the_least_recently_used() {
+        let mut cache = BeadsCache::with_max_issues(2);
+        cache.apply_event(&created_event("bd-1"));
+        cache.apply_event(&created_event("bd-2"));
+        cache.apply_event(&created_event("bd-3"));
+
+        assert!(cache.get_issue("bd-1").is_none());
+        assert!(cache.get_issue("bd-2").is_some());
+        assert!(cache.get_issue("bd-3").is_some());
+    }
+
+    #[test]
+    fn a_get_refreshes_recency() {
+        let mut cache = BeadsCache::with_max_issues(2);
+        cache.apply_event(&created_event("bd-1"));
+        cache.apply_event(&created_event("bd-2"));
+
+        // Touch bd-1 so bd-2 becomes the eviction candidate.
+        assert!(cache.get_issue("bd-1").is_some());
+        cache.apply_event(&created_event("bd-3"));
+
+        assert!(cache.get_issue("bd-1").is_some());
+        assert!(cache.get_issue("bd-2").is_none());
+        assert!(cache.get_issue("bd-3").is_some());
+    }
+
+    #[test]
+    fn full_refresh_respects_the_cap_in_input_order() {
+        let mut cache = BeadsCache::with_max_issues(2);
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "a", "status": "open"})),
+                issue(json!({"id": "bd-2", "title": "b", "status": "open"})),
+                issue(json!({"id": "bd-3", "title": "c", "status": "open"})),
+            ],
+            vec![],
+            vec![],
+        );
+        assert!(cache.get_issue("bd-1").is_none());
+        assert_eq!(cache.list_issues().len(), 2);
+    }
+
+    #[test]
+    fn compressed_cache_round_trips_and_migrates_the_legacy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = BeadsCache::cache_file_path(dir.path());
+        let legacy = BeadsCache::legacy_cache_file_path(dir.path());
+        std::fs::write(&legacy, b"{}").unwrap();
+
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![issue(json!({"id": "bd-1", "title": "round trip", "status": "open"}))],
+            vec![],
+            vec![],
+        );
+        cache.save_to_disk(&path).unwrap();
+
+        // The file on disk really is gzip, and the legacy copy is gone.
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(&GZIP_MAGIC));
+        assert!(!legacy.exists());
+
+        let mut loaded = BeadsCache::new();
+        loaded.load_from_disk(&path).unwrap();
+        assert_eq!(loaded.get_issue("bd-1").unwrap().title, "round trip");
+    }
+
+    #[test]
+    fn legacy_uncompressed_cache_still_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = BeadsCache::legacy_cache_file_path(dir.path());
+
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![issue(json!({"id": "bd-1", "title": "t", "status": "open"}))],
+            vec![],
+            vec![],
+        );
+        std::fs::write(&legacy, serde_json::to_vec(&cache.to_serialized()).unwrap()).unwrap();
+
+        // Loading via the compressed path falls through to the legacy file.
+        let mut loaded = BeadsCache::new();
+        loaded
+            .load_from_disk(&BeadsCache::cache_file_path(dir.path()))
+            .unwrap();
+        assert!(loaded.get_issue("bd-1").is_some());
+    }
+
+    #[test]
+    fn truncated_cache_file_falls_back_to_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = BeadsCache::cache_file_path(dir.path());
+
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![issue(json!({"id": "bd-1", "title": "t", "status": "open"}))],
+            vec![],
+            vec![],
+        );
+        cache.save_to_disk(&path).unwrap();
+        // A second save snapshots the first good copy as .bak.
+        cache.save_to_disk(&path).unwrap();
+
+        // Simulate a partial write: chop the main file mid-JSON.
+        let good = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &good[..good.len() / 2]).unwrap();
+
+        let mut loaded = BeadsCache::new();
+        loaded.load_from_disk(&path).unwrap();
+        assert!(loaded.get_issue("bd-1").is_some());
+    }
+
+    #[test]
+    fn corrupt_cache_without_backup_errors_instead_of_panicking() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = BeadsCache::cache_file_path(dir.path());
+        std::fs::write(&path, b"{ not json").unwrap();
+
+        let mut cache = BeadsCache::new();
+        assert!(cache.load_from_disk(&path).is_err());
+    }
+
+    #[test]
+    fn last_event_ts_survives_a_save_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = BeadsCache::cache_file_path(dir.path());
+
+        let mut cache = BeadsCache::new();
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "issue.created",
+            "issue_id": "bd-1",
+            "timestamp": "2024-03-01T12:00:00Z",
+            "issue": {"id": "bd-1", "title": "t", "status": "open"}
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+        assert_eq!(cache.last_event_ts(), Some("2024-03-01T12:00:00Z"));
+        cache.save_to_disk(&path).unwrap();
+
+        let mut loaded = BeadsCache::new();
+        loaded.load_from_disk(&path).unwrap();
+        assert_eq!(loaded.last_event_ts(), Some("2024-03-01T12:00:00Z"));
+    }
+
+    #[test]
+    fn cache_file_without_last_event_ts_still_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = BeadsCache::cache_file_path(dir.path());
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&json!({
+                "issues": [], "gates": [], "epics": [], "last_full_sync": null
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut cache = BeadsCache::new();
+        cache.load_from_disk(&path).unwrap();
+        assert_eq!(cache.last_event_ts(), None);
+    }
+
+    #[test]
+    fn priority_sort_is_numeric_with_missing_values_last() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "a", "status": "open", "priority": 10})),
+                issue(json!({"id": "bd-2", "title": "b", "status": "open", "priority": 2})),
+                issue(json!({"id": "bd-3", "title": "c", "status": "open"})),
+            ],
+            vec![],
+            vec![],
+        );
+
+        // Numeric, not lexical: 2 before 10, None last.
+        let sorted = cache.list_issues_sorted(SortKey::Priority, false);
+        let ids: Vec<&str> = sorted.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["bd-2", "bd-1", "bd-3"]);
+
+        // Descending still keeps the unprioritized issue at the bottom.
+        let sorted = cache.list_issues_sorted(SortKey::Priority, true);
+        let ids: Vec<&str> = sorted.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["bd-1", "bd-2", "bd-3"]);
+    }
+
+    #[test]
+    fn title_sort_honors_direction() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({"id": "bd-1", "title": "zebra", "status": "open"})),
+                issue(json!({"id": "bd-2", "title": "apple", "status": "open"})),
+            ],
+            vec![],
+            vec![],
+        );
+        let sorted = cache.list_issues_sorted(SortKey::Title, true);
+        assert_eq!(sorted[0].title, "zebra");
+    }
+
+    #[test]
+    fn advanced_search_filters_are_anded() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue(json!({
+                    "id": "bd-1", "title": "fix login crash", "status": "open",
+                    "labels": ["bug", "auth"], "assignee": "alice"
+                })),
+                issue(json!({
+                    "id": "bd-2", "title": "login polish", "status": "open",
+                    "labels": ["bug"], "assignee": "bob"
+                })),
+                issue(json!({
+                    "id": "bd-3", "title": "docs", "status": "closed",
+                    "labels": ["bug", "auth"]
+                })),
+            ],
+            vec![],
+            vec![],
+        );
+
+        // Label-only: both requested labels must be present.
+        let labels = vec!["bug".to_string(), "auth".to_string()];
+        let hits = cache.search_issues_advanced("", &labels, None, None);
+        let mut ids: Vec<&str> = hits.iter().map(|i| i.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["bd-1", "bd-3"]);
+
+        // Query + assignee combined.
+        let hits = cache.search_issues_advanced("login", &[], None, Some("alice"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "bd-1");
+
+        // Nothing satisfies every filter at once.
+        let hits = cache.search_issues_advanced("docs", &[], Some("open"), None);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn payload_less_event_goes_through_the_fetcher() {
+        let requested: Arc<std::sync::Mutex<Vec<String>>> =
+            Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let mut cache = BeadsCache::new();
+        let seen = requested.clone();
+        cache.set_missing_issue_fetcher(Arc::new(move |id: &str| {
+            seen.lock().unwrap().push(id.to_string());
+        }));
+
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "issue.updated",
+            "issue_id": "bd-1"
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+
+        // The fetcher was asked for the missing issue; once its out-of-band
+        // fetch lands the cache is populated.
+        assert_eq!(*requested.lock().unwrap(), vec!["bd-1"]);
+        assert!(cache.get_issue("bd-1").is_none());
+        cache.upsert_issue(issue(json!({"id": "bd-1", "title": "t", "status": "open"})));
+        assert!(cache.get_issue("bd-1").is_some());
+    }
+
+    #[test]
+    fn apply_event_upserts_embedded_epic() {
+        let mut cache = BeadsCache::new();
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "epic.created",
+            "epic": {
+                "epic_id": "bd-e", "title": "Epic", "total_issues": 3,
+                "closed_issues": 0, "in_progress": 1, "blocked": 0
+            }
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+        assert_eq!(cache.get_epic_status("bd-e").unwrap().total_issues, 3);
+
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "epic.updated",
+            "epic": {
+                "epic_id": "bd-e", "title": "Epic", "total_issues": 3,
+                "closed_issues": 2, "in_progress": 0, "blocked": 0
+            }
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+        assert_eq!(cache.get_epic_status("bd-e").unwrap().closed_issues, 2);
+    }
+
+    #[test]
+    fn apply_event_removes_deleted_epic() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(vec![], vec![], vec![epic("bd-e")]);
+        assert!(cache.get_epic_status("bd-e").is_some());
+
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "epic.deleted",
+            "epic_id": "bd-e"
+        }))
+        .unwrap();
+        cache.apply_event(&event);
+        assert!(cache.get_epic_status("bd-e").is_none());
+    }
+}
@@ -0,0 +1,1707 @@
+//! Async client for the `bd` CLI.
+//!
+//! Reads run freely; writes are funnelled through a single-permit semaphore
+//! (`run_bd_write`) so concurrent UI actions can't interleave bd mutations.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::{mpsc, Semaphore};
+
+use super::types::{Comment, EpicStatus, Gate, Issue};
+
+/// Timeout applied to every bd invocation.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for read-heavy subcommands that legitimately run long on big
+/// workspaces (`list`, `ready`, …).
+const LONG_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout for quick probes (`daemon status`); these should fail fast so
+/// health checks don't hang the UI.
+const SHORT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Per-subcommand timeout table, keyed by bd's first argument. Anything
+/// not listed falls back to [`DEFAULT_TIMEOUT`].
+fn default_timeouts() -> HashMap<&'static str, Duration> {
+    HashMap::from([
+        ("list", LONG_TIMEOUT),
+        ("ready", LONG_TIMEOUT),
+        ("search", LONG_TIMEOUT),
+        ("dep", LONG_TIMEOUT),
+        ("daemon", SHORT_TIMEOUT),
+    ])
+}
+
+/// How long an identical read is served from the micro-cache instead of
+/// spawning bd again. Short on purpose: this only dedupes rapid repeats
+/// (component mount/unmount churn), it is not the domain cache.
+const READ_CACHE_TTL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Error)]
+pub enum BdError {
+    #[error("bd CLI not found (checked: {})", checked_paths.join(", "))]
+    CliNotFound { checked_paths: Vec<String> },
+    #[error("bd command failed: {stderr}")]
+    CommandFailed { stderr: String },
+    #[error("failed to parse bd output: {0}")]
+    ParseError(#[from] serde_json::Error),
+    #[error("bd command timed out after {0:?}")]
+    Timeout(Duration),
+    /// Rejected before bd was invoked (e.g. an out-of-range priority).
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+    /// The client was shut down (workspace switch) while this write was
+    /// still queued.
+    #[error("bd client closed before the command ran")]
+    Closed,
+    #[error("io error running bd: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl BdError {
+    /// Stable machine-readable code for this error; the command layer ships
+    /// it to the frontend so remediation hints don't parse message text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::CliNotFound { .. } => "cli_not_found",
+            Self::CommandFailed { .. } => "command_failed",
+            Self::ParseError(_) => "parse_error",
+            Self::Timeout(_) => "timeout",
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::Closed => "closed",
+            Self::Io(_) => "io_error",
+        }
+    }
+}
+
+pub type BdResult<T> = Result<T, BdError>;
+
+/// One stderr progress line from a long-running bd command. bd emits JSON
+/// (`{"progress": 0.4, "message": "..."}`); anything else is passed through
+/// as a bare message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// One page of issues plus enough bookkeeping for the frontend to render
+/// page controls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuePage {
+    pub issues: Vec<Issue>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// Name of the bd executable on this platform.
+pub(crate) const BD_EXE: &str = if cfg!(windows) { "bd.exe" } else { "bd" };
+
+/// Locate the bd binary. On unix: `~/.local/bin/bd`, then PATH. On Windows:
+/// `%LOCALAPPDATA%\bd\bd.exe`, `%USERPROFILE%\bd.exe`, then PATH. The `Err`
+/// carries every path actually tried so `CliNotFound` stays honest about
+/// the platform.
+pub fn find_bd_binary() -> Result<PathBuf, Vec<String>> {
+    let mut checked = Vec::new();
+    if cfg!(windows) {
+        if let Some(local_appdata) = std::env::var_os("LOCALAPPDATA") {
+            let candidate = PathBuf::from(local_appdata).join("bd").join(BD_EXE);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+            checked.push(candidate.display().to_string());
+        }
+        if let Some(home) = dirs::home_dir() {
+            let candidate = home.join(BD_EXE);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+            checked.push(candidate.display().to_string());
+        }
+    } else if let Some(home) = dirs::home_dir() {
+        let local = home.join(".local/bin").join(BD_EXE);
+        if local.is_file() {
+            return Ok(local);
+        }
+        checked.push(local.display().to_string());
+    }
+    if let Some(paths) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&paths) {
+            let candidate = dir.join(BD_EXE);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+    }
+    checked.push(format!("{BD_EXE} (PATH)"));
+    Err(checked)
+}
+
+pub struct BdClient {
+    bd_path: PathBuf,
+    workspace: PathBuf,
+    default_timeout: Duration,
+    /// Per-subcommand timeout overrides, keyed by bd's first argument.
+    timeouts: HashMap<&'static str, Duration>,
+    /// Write-concurrency gate. Defaults to a single permit — bd writes are
+    /// serialized to avoid daemon races — but can be widened via
+    /// [`BdClient::with_write_concurrency`] / [`BdClient::set_write_concurrency`]
+    /// for workspaces where bd handles concurrent writes safely.
+    write_semaphore: RwLock<Arc<Semaphore>>,
+    /// Assignee applied to created/claimed issues when the caller doesn't
+    /// specify one. See [`BdClient::set_default_assignee`].
+    default_assignee: RwLock<Option<String>>,
+    /// TTL micro-cache for read commands, keyed by the full argument list.
+    read_cache: Mutex<HashMap<String, (Instant, Value)>>,
+    /// Acting user recorded on gate resolutions via `--by`.
+    current_user: RwLock<Option<String>>,
+    /// bd version detected on first use, for feature gating. The inner
+    /// `None` means bd answered with something unparseable (or not at all);
+    /// we remember that too rather than re-probing on every call.
+    detected_version: std::sync::OnceLock<Option<semver::Version>>,
+}
+
+impl BdClient {
+    /// Build a client for `workspace`, locating the bd binary.
+    pub fn new(workspace: impl Into<PathBuf>) -> BdResult<Self> {
+        let bd_path = find_bd_binary()
+            .map_err(|checked_paths| BdError::CliNotFound { checked_paths })?;
+        Ok(Self::with_binary(bd_path, workspace))
+    }
+
+    /// Build a client around an explicit bd binary path. Used when the
+    /// caller (or a test) already knows where bd lives.
+    pub fn with_binary(bd_path: impl Into<PathBuf>, workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            bd_path: bd_path.into(),
+            workspace: workspace.into(),
+            default_timeout: DEFAULT_TIMEOUT,
+            timeouts: default_timeouts(),
+            write_semaphore: RwLock::new(Arc::new(Semaphore::new(1))),
+            default_assignee: RwLock::new(None),
+            read_cache: Mutex::new(HashMap::new()),
+            current_user: RwLock::new(None),
+            detected_version: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Build a client for `workspace` allowing up to `permits` concurrent bd
+    /// writes. The plain constructors keep the default of 1; only raise this
+    /// for workspaces where bd is known to handle concurrent writes safely.
+    pub fn with_write_concurrency(
+        workspace: impl Into<PathBuf>,
+        permits: usize,
+    ) -> BdResult<Self> {
+        let client = Self::new(workspace)?;
+        client.set_write_concurrency(permits);
+        Ok(client)
+    }
+
+    /// Replace the write semaphore with one holding `permits` permits
+    /// (clamped to at least 1). Writes already queued stay on the old
+    /// semaphore and finish under the old limit.
+    pub fn set_write_concurrency(&self, permits: usize) {
+        *self.write_semaphore.write().unwrap() = Arc::new(Semaphore::new(permits.max(1)));
+    }
+
+    /// Set (or clear) the acting user recorded on gate resolutions.
+    pub fn set_current_user(&self, user: Option<String>) {
+        *self.current_user.write().unwrap() = user;
+    }
+
+    pub fn current_user(&self) -> Option<String> {
+        self.current_user.read().unwrap().clone()
+    }
+
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    pub fn bd_path(&self) -> &Path {
+        &self.bd_path
+    }
+
+    /// Where the daemon's unix socket lives for this workspace.
+    pub fn socket_path(&self) -> PathBuf {
+        super::daemon::DaemonManager::with_binary(&self.bd_path, &self.workspace).socket_path()
+    }
+
+    /// Whether the daemon socket exists — a cheap proxy for "daemon
+    /// running" suitable for frequent polling, since it's a single stat
+    /// instead of a process spawn. Always false on Windows, where bd has no
+    /// unix socket; callers there must fall back to the status probe.
+    pub fn socket_exists(&self) -> bool {
+        cfg!(unix) && self.socket_path().exists()
+    }
+
+    /// Set (or clear) the assignee applied when `create_issue`/`claim_issue`
+    /// are called without an explicit one.
+    pub fn set_default_assignee(&self, assignee: Option<String>) {
+        *self.default_assignee.write().unwrap() = assignee;
+    }
+
+    pub fn default_assignee(&self) -> Option<String> {
+        self.default_assignee.read().unwrap().clone()
+    }
+
+    /// The bd version, probed with `bd --version` on first call and cached
+    /// for the client's lifetime. `None` when bd is missing or prints
+    /// something unrecognizable.
+    pub async fn bd_version(&self) -> Option<semver::Version> {
+        if let Some(cached) = self.detected_version.get() {
+            return cached.clone();
+        }
+        let detected = match Command::new(&self.bd_path)
+            .arg("--version")
+            .current_dir(&self.workspace)
+            .kill_on_drop(true)
+            .output()
+            .await
+        {
+            Ok(output) => parse_bd_version(&String::from_utf8_lossy(&output.stdout)),
+            Err(err) => {
+                tracing::debug!("bd --version failed: {err}");
+                None
+            }
+        };
+        self.detected_version.get_or_init(|| detected).clone()
+    }
+
+    /// Whether bd understands `activity --since`, added in 0.40. Unknown
+    /// versions are treated as too old so we degrade rather than error.
+    pub async fn supports_since_flag(&self) -> bool {
+        self.bd_version()
+            .await
+            .is_some_and(|v| v >= semver::Version::new(0, 40, 0))
+    }
+
+    /// Explicit assignee wins; otherwise fall back to the configured default.
+    fn resolve_assignee(&self, explicit: Option<&str>) -> Option<String> {
+        explicit
+            .map(|a| a.to_string())
+            .or_else(|| self.default_assignee())
+    }
+
+    /// Timeout for one invocation: the per-subcommand table keyed by the
+    /// first argument, falling back to the client's default.
+    fn timeout_for(&self, args: &[&str]) -> Duration {
+        args.first()
+            .and_then(|cmd| self.timeouts.get(cmd).copied())
+            .unwrap_or(self.default_timeout)
+    }
+
+    /// Run bd with `args`, expecting JSON on stdout. Anything bd printed to
+    /// stderr on success is logged at debug level and dropped.
+    async fn run_bd_json(&self, args: &[&str]) -> BdResult<Value> {
+        let (value, _stderr) = self.run_bd_json_with_stderr(args).await?;
+        Ok(value)
+    }
+
+    /// Like `run_bd_json` but hands back stderr from a successful run as
+    /// well. Some bd versions print deprecation or daemon-restart warnings
+    /// before the JSON; callers diagnosing parse weirdness want to see them.
+    async fn run_bd_json_with_stderr(&self, args: &[&str]) -> BdResult<(Value, Option<String>)> {
+        let timeout = self.timeout_for(args);
+        let output = tokio::time::timeout(
+            timeout,
+            Command::new(&self.bd_path)
+                .args(args)
+                .current_dir(&self.workspace)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .map_err(|_| BdError::Timeout(timeout))??;
+
+        if !output.status.success() {
+            return Err(BdError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stderr = if stderr.is_empty() {
+            None
+        } else {
+            tracing::debug!("bd {:?} succeeded with stderr: {stderr}", args.first());
+            Some(stderr)
+        };
+        Ok((parse_bd_output(&output.stdout)?, stderr))
+    }
+
+    /// Like `run_bd_json`, but streams stderr lines to `progress` while the
+    /// command runs, for long operations that report progress out-of-band.
+    /// The plain `run_bd_json` path stays silent about stderr on success.
+    pub async fn run_bd_json_with_progress(
+        &self,
+        args: &[&str],
+        progress: mpsc::Sender<ProgressUpdate>,
+    ) -> BdResult<Value> {
+        use std::process::Stdio;
+
+        let mut child = Command::new(&self.bd_path)
+            .args(args)
+            .current_dir(&self.workspace)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stderr = child.stderr.take().expect("stderr piped");
+        let forwarder = tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let update = serde_json::from_str::<ProgressUpdate>(&line).unwrap_or_else(|_| {
+                    ProgressUpdate {
+                        progress: None,
+                        message: line,
+                    }
+                });
+                if progress.send(update).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let timeout = self.timeout_for(args);
+        let result = tokio::time::timeout(timeout, async {
+            let mut stdout = child.stdout.take().expect("stdout piped");
+            let mut buf = Vec::new();
+            stdout.read_to_end(&mut buf).await?;
+            let status = child.wait().await?;
+            Ok::<_, std::io::Error>((status, buf))
+        })
+        .await
+        .map_err(|_| BdError::Timeout(timeout))??;
+        let _ = forwarder.await;
+
+        let (status, stdout) = result;
+        if !status.success() {
+            // stderr was consumed by the progress channel.
+            return Err(BdError::CommandFailed {
+                stderr: format!("bd exited with {status}"),
+            });
+        }
+        Ok(parse_bd_output(&stdout)?)
+    }
+
+    /// Like `run_bd_json`, but identical invocations within
+    /// [`READ_CACHE_TTL`] are served from the micro-cache without spawning
+    /// bd. Only for read commands; writes must go through `run_bd_write`.
+    async fn run_bd_json_cached(&self, args: &[&str]) -> BdResult<Value> {
+        let key = args.join("\u{1f}");
+        if let Some((at, value)) = self.read_cache.lock().unwrap().get(&key) {
+            if at.elapsed() < READ_CACHE_TTL {
+                return Ok(value.clone());
+            }
+        }
+        let value = self.run_bd_json(args).await?;
+        self.read_cache
+            .lock()
+            .unwrap()
+            .insert(key, (Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    /// Stop accepting writes on this client. Queued `run_bd_write` callers
+    /// wake up with [`BdError::Closed`]; the write currently in flight (if
+    /// any) finishes against this client's workspace, which is where it was
+    /// aimed. Called when the workspace switches away from this client.
+    pub fn close_writes(&self) {
+        self.write_semaphore.read().unwrap().close();
+    }
+
+    /// Run a mutating bd command, holding exactly one write permit for its
+    /// duration — widening the semaphore raises parallelism without letting
+    /// any single write hog the gate.
+    async fn run_bd_write(&self, args: &[&str]) -> BdResult<Value> {
+        let semaphore = self.write_semaphore.read().unwrap().clone();
+        let _permit = match semaphore.acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => return Err(BdError::Closed),
+        };
+        let result = self.run_bd_json(args).await;
+        // Any write invalidates the whole read micro-cache: entries only
+        // live for a couple of seconds, so correctness beats fine-grained
+        // key tracking here.
+        self.read_cache.lock().unwrap().clear();
+        result
+    }
+
+    pub async fn list_issues(&self) -> BdResult<Vec<Issue>> {
+        let value = self.run_bd_json_cached(&["list", "--json"]).await?;
+        issues_from_value(value)
+    }
+
+    /// One page of the issue list. Newer bd versions page server-side via
+    /// `--limit`/`--offset` and report the total in the response wrapper;
+    /// when bd rejects the flags (or omits the total) we fetch the full list
+    /// once and slice it here, so the caller sees the same shape either way.
+    pub async fn list_issues_paged(&self, offset: usize, limit: usize) -> BdResult<IssuePage> {
+        let limit_s = limit.to_string();
+        let offset_s = offset.to_string();
+        let args = ["list", "--limit", &limit_s, "--offset", &offset_s, "--json"];
+        if let Ok(value) = self.run_bd_json_cached(&args).await {
+            let total = value.get("total").and_then(Value::as_u64);
+            if let Some(total) = total {
+                let issues = issues_from_value(value)?;
+                return Ok(IssuePage {
+                    issues,
+                    total: total as usize,
+                    offset,
+                    limit,
+                });
+            }
+        }
+        let all = self.list_issues().await?;
+        Ok(Self::slice_page(all, offset, limit))
+    }
+
+    /// Client-side fallback paging over a fully materialized list.
+    fn slice_page(all: Vec<Issue>, offset: usize, limit: usize) -> IssuePage {
+        let total = all.len();
+        let issues = all
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect();
+        IssuePage {
+            issues,
+            total,
+            offset,
+            limit,
+        }
+    }
+
+    /// `list_issues` with server-side filtering: each filter maps to the
+    /// matching bd flag, and bd ANDs them together. All-`None` is identical
+    /// to [`BdClient::list_issues`].
+    pub async fn list_issues_filtered(
+        &self,
+        status: Option<&str>,
+        assignee: Option<&str>,
+        label: Option<&str>,
+    ) -> BdResult<Vec<Issue>> {
+        let args = Self::build_list_args(status, assignee, label);
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let value = self.run_bd_json_cached(&arg_refs).await?;
+        issues_from_value(value)
+    }
+
+    fn build_list_args(
+        status: Option<&str>,
+        assignee: Option<&str>,
+        label: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = vec!["list".to_string()];
+        if let Some(status) = status {
+            args.push("--status".to_string());
+            args.push(status.to_string());
+        }
+        if let Some(assignee) = assignee {
+            args.push("--assignee".to_string());
+            args.push(assignee.to_string());
+        }
+        if let Some(label) = label {
+            args.push("--label".to_string());
+            args.push(label.to_string());
+        }
+        args.push("--json".to_string());
+        args
+    }
+
+    pub async fn get_issue(&self, id: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self.run_bd_json_cached(&["show", id, "--json"]).await?;
+        issue_from_value(value)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_issue(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        labels: &[String],
+        parent: Option<&str>,
+        deps: &[String],
+        assignee: Option<&str>,
+        priority: Option<u8>,
+    ) -> BdResult<Issue> {
+        if let Some(p) = priority {
+            if p > 4 {
+                return Err(BdError::InvalidArgument(format!(
+                    "priority must be between 0 and 4, got {p}"
+                )));
+            }
+        }
+        let args =
+            self.build_create_args(title, description, labels, parent, deps, assignee, priority);
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let value = self.run_bd_write(&arg_refs).await?;
+        issue_from_value(value)
+    }
+
+    /// Assemble the `bd create` argument list. Split out so the assignee
+    /// resolution is testable without spawning bd.
+    #[allow(clippy::too_many_arguments)]
+    fn build_create_args(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        labels: &[String],
+        parent: Option<&str>,
+        deps: &[String],
+        assignee: Option<&str>,
+        priority: Option<u8>,
+    ) -> Vec<String> {
+        let mut args = vec!["create".to_string()];
+        if let Some(desc) = description {
+            args.push("--description".to_string());
+            args.push(desc.to_string());
+        }
+        for label in labels {
+            args.push("--label".to_string());
+            args.push(label.clone());
+        }
+        if let Some(parent) = parent {
+            args.push("--parent".to_string());
+            args.push(parent.to_string());
+        }
+        if !deps.is_empty() {
+            args.push("--deps".to_string());
+            args.push(deps.join(","));
+        }
+        if let Some(assignee) = self.resolve_assignee(assignee) {
+            args.push("--assignee".to_string());
+            args.push(assignee);
+        }
+        if let Some(priority) = priority {
+            args.push("--priority".to_string());
+            args.push(priority.to_string());
+        }
+        args.push("--json".to_string());
+        // Title goes last so a `--` separator can precede it when needed.
+        push_positional(&mut args, title);
+        args
+    }
+
+    pub async fn update_issue_status(&self, id: &str, status: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["update", id, "--status", status, "--json"])
+            .await?;
+        issue_from_value(value)
+    }
+
+    /// Move several issues to `status` under a single write permit. Tries
+    /// bd's comma-separated ID form first and falls back to one `bd update`
+    /// per issue when that isn't supported, so partial failures stay visible
+    /// in the per-issue results.
+    pub async fn bulk_update_status(
+        &self,
+        ids: &[&str],
+        status: &str,
+    ) -> BdResult<Vec<BdResult<Issue>>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        for id in ids {
+            validate_id(id)?;
+        }
+        let semaphore = self.write_semaphore.read().unwrap().clone();
+        let _permit = match semaphore.acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => return Err(BdError::Closed),
+        };
+
+        let joined = ids.join(",");
+        let batch = self
+            .run_bd_json(&["update", &joined, "--status", status, "--json"])
+            .await
+            .and_then(issues_from_value);
+        let results = match batch {
+            Ok(issues) if issues.len() == ids.len() => issues.into_iter().map(Ok).collect(),
+            // Unsupported comma form, or a shape we don't recognize: do it
+            // the slow way so each issue gets its own verdict.
+            _ => {
+                let mut out = Vec::with_capacity(ids.len());
+                for id in ids {
+                    let res = self
+                        .run_bd_json(&["update", id, "--status", status, "--json"])
+                        .await
+                        .and_then(issue_from_value);
+                    out.push(res);
+                }
+                out
+            }
+        };
+
+        self.read_cache.lock().unwrap().clear();
+        Ok(results)
+    }
+
+    pub async fn assign_issue(&self, id: &str, assignee: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["update", id, "--assignee", assignee, "--json"])
+            .await?;
+        issue_from_value(value)
+    }
+
+    /// Clear an issue's assignee. bd has no dedicated unassign flag, so this
+    /// writes an empty `--assignee`; the returned issue comes back with
+    /// `assignee: null` and `effective_assignee()` falls back to `owner`.
+    pub async fn unassign_issue(&self, id: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["update", id, "--assignee", "", "--json"])
+            .await?;
+        issue_from_value(value)
+    }
+
+    pub async fn close_issue(&self, id: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self.run_bd_write(&["close", id, "--json"]).await?;
+        issue_from_value(value)
+    }
+
+    /// Reopen a closed issue. bd surfaces its own error (passed through
+    /// verbatim in [`BdError::CommandFailed`]) when the issue isn't closed.
+    pub async fn reopen_issue(&self, id: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self.run_bd_write(&["reopen", id, "--json"]).await?;
+        issue_from_value(value)
+    }
+
+    /// Permanently delete an issue. bd's response varies by version (empty
+    /// array, bare object, or `{"deleted": true}`); the normalized raw value
+    /// is returned since there is no issue left to parse.
+    pub async fn delete_issue(&self, id: &str) -> BdResult<Value> {
+        validate_id(id)?;
+        let value = self.run_bd_write(&["delete", id, "--json"]).await?;
+        Ok(unwrap_entity(value, "deleted"))
+    }
+
+    /// Claim an issue for `assignee`, falling back to the configured default
+    /// assignee, and finally to bd's own notion of the current user.
+    pub async fn claim_issue(&self, id: &str, assignee: Option<&str>) -> BdResult<Issue> {
+        validate_id(id)?;
+        let mut args = vec!["claim".to_string(), id.to_string()];
+        if let Some(assignee) = self.resolve_assignee(assignee) {
+            args.push("--assignee".to_string());
+            args.push(assignee);
+        }
+        args.push("--json".to_string());
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let value = self.run_bd_write(&arg_refs).await?;
+        issue_from_value(value)
+    }
+
+    /// Comment thread for one issue, oldest first as bd returns it.
+    pub async fn get_comments(&self, id: &str) -> BdResult<Vec<Comment>> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_json_cached(&["comment", "list", id, "--json"])
+            .await?;
+        comments_from_value(value)
+    }
+
+    /// Post a comment on an issue. Returns the created comment.
+    pub async fn add_comment(&self, id: &str, body: &str) -> BdResult<Comment> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["comment", "add", id, "--body", body, "--json"])
+            .await?;
+        comment_from_value(value)
+    }
+
+    /// Add `label` to an issue without round-tripping the full label set
+    /// through `bd update`. Returns the updated issue.
+    pub async fn add_label(&self, id: &str, label: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["label", "add", id, label, "--json"])
+            .await
+            .map_err(|err| friendly_label_error(err, id, label))?;
+        issue_from_value(value)
+    }
+
+    /// Remove `label` from an issue. Returns the updated issue.
+    pub async fn remove_label(&self, id: &str, label: &str) -> BdResult<Issue> {
+        validate_id(id)?;
+        let value = self
+            .run_bd_write(&["label", "remove", id, label, "--json"])
+            .await
+            .map_err(|err| friendly_label_error(err, id, label))?;
+        issue_from_value(value)
+    }
+
+    /// Make `issue_id` depend on `depends_on`. Returns the updated issue so
+    /// the caller can refresh its DAG node.
+    pub async fn add_dependency(&self, issue_id: &str, depends_on: &str) -> BdResult<Issue> {
+        validate_id(issue_id)?;
+        validate_id(depends_on)?;
+        let value = self
+            .run_bd_write(&["dep", "add", issue_id, depends_on, "--json"])
+            .await?;
+        issue_from_value(value)
+    }
+
+    /// Remove the `issue_id` → `depends_on` dependency. Returns the updated
+    /// issue.
+    pub async fn remove_dependency(&self, issue_id: &str, depends_on: &str) -> BdResult<Issue> {
+        validate_id(issue_id)?;
+        validate_id(depends_on)?;
+        let value = self
+            .run_bd_write(&["dep", "remove", issue_id, depends_on, "--json"])
+            .await?;
+        issue_from_value(value)
+    }
+
+    /// Enumerate epics directly from bd rather than inferring them from
+    /// issue IDs. Feeds `BeadsCache::full_refresh`.
+    pub async fn list_epics(&self) -> BdResult<Vec<EpicStatus>> {
+        let value = self.run_bd_json_cached(&["epics", "--json"]).await?;
+        epics_from_value(value)
+    }
+
+    pub async fn list_gates(&self) -> BdResult<Vec<Gate>> {
+        let value = self.run_bd_json_cached(&["gate", "list", "--json"]).await?;
+        gates_from_value(value)
+    }
+
+    /// Authoritative state of a single gate, straight from bd. Older bd
+    /// versions have no `gate show`; for those we fall back to filtering the
+    /// full gate list.
+    pub async fn get_gate(&self, gate_id: &str) -> BdResult<Gate> {
+        validate_id(gate_id)?;
+        if let Ok(value) = self
+            .run_bd_json_cached(&["gate", "show", gate_id, "--json"])
+            .await
+        {
+            return gate_from_value(value);
+        }
+        let gates = self.list_gates().await?;
+        gates
+            .into_iter()
+            .find(|g| g.id == gate_id)
+            .ok_or_else(|| BdError::CommandFailed {
+                stderr: format!("gate not found: {gate_id}"),
+            })
+    }
+
+    pub async fn resolve_gate(&self, gate_id: &str, reason: &str) -> BdResult<Gate> {
+        validate_id(gate_id)?;
+        let args = self.build_resolve_gate_args(gate_id, reason);
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let value = self.run_bd_write(&arg_refs).await?;
+        gate_from_value(value)
+    }
+
+    /// `bd resolve-gate` arguments, recording the acting user with `--by`
+    /// when one is configured.
+    fn build_resolve_gate_args(&self, gate_id: &str, reason: &str) -> Vec<String> {
+        let mut args = vec![
+            "resolve-gate".to_string(),
+            gate_id.to_string(),
+            "--reason".to_string(),
+            reason.to_string(),
+        ];
+        if let Some(user) = self.current_user() {
+            args.push("--by".to_string());
+            args.push(user);
+        }
+        args.push("--json".to_string());
+        args
+    }
+
+    /// Resolve several gates with one shared reason under a single write
+    /// permit, so a batch approval doesn't queue behind itself. Individual
+    /// failures don't abort the batch; each gate gets its own verdict in
+    /// the returned results, in input order.
+    pub async fn resolve_gates(
+        &self,
+        gate_ids: &[&str],
+        reason: &str,
+    ) -> BdResult<Vec<BdResult<Gate>>> {
+        if gate_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        for id in gate_ids {
+            validate_id(id)?;
+        }
+        let semaphore = self.write_semaphore.read().unwrap().clone();
+        let _permit = match semaphore.acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => return Err(BdError::Closed),
+        };
+
+        let mut results = Vec::with_capacity(gate_ids.len());
+        for gate_id in gate_ids {
+            let args = self.build_resolve_gate_args(gate_id, reason);
+            let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+            let res = self.run_bd_json(&arg_refs).await.and_then(gate_from_value);
+            results.push(res);
+        }
+
+        self.read_cache.lock().unwrap().clear();
+        Ok(results)
+    }
+
+    /// Reject a gate with a reason. A distinct verb from [`Self::resolve_gate`]
+    /// because bd records the outcome differently — the returned gate's
+    /// status reflects the rejection.
+    pub async fn reject_gate(&self, gate_id: &str, reason: &str) -> BdResult<Gate> {
+        validate_id(gate_id)?;
+        let args = self.build_reject_gate_args(gate_id, reason);
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let value = self.run_bd_write(&arg_refs).await?;
+        gate_from_value(value)
+    }
+
+    /// `bd reject-gate` arguments, recording the acting user with `--by`
+    /// when one is configured.
+    fn build_reject_gate_args(&self, gate_id: &str, reason: &str) -> Vec<String> {
+        let mut args = vec![
+            "reject-gate".to_string(),
+            gate_id.to_string(),
+            "--reason".to_string(),
+            reason.to_string(),
+        ];
+        if let Some(user) = self.current_user() {
+            args.push("--by".to_string());
+            args.push(user);
+        }
+        args.push("--json".to_string());
+        args
+    }
+
+    /// Register a new workspace by running `bd init --json` inside it.
+    /// Deliberately not `run_bd_write`: the target is by definition a
+    /// directory other than this client's workspace, so the write gate and
+    /// read cache don't apply.
+    pub async fn init_workspace(&self, workspace: &Path) -> BdResult<Value> {
+        let output = tokio::time::timeout(
+            self.default_timeout,
+            Command::new(&self.bd_path)
+                .args(["init", "--json"])
+                .current_dir(workspace)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .map_err(|_| BdError::Timeout(self.default_timeout))??;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            // bd exits non-zero when the directory is already registered;
+            // surface that as a usage error, not a generic failure.
+            if stderr.to_lowercase().contains("already") {
+                return Err(BdError::InvalidArgument(format!(
+                    "{} is already registered with bd",
+                    workspace.display()
+                )));
+            }
+            return Err(BdError::CommandFailed { stderr });
+        }
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+/// A user-supplied positional beginning with `-` would be parsed by bd as a
+/// flag (a title of `--status` makes `bd create` fail confusingly). Emitting
+/// the conventional `--` separator in front keeps it positional.
+fn push_positional(args: &mut Vec<String>, value: &str) {
+    if value.starts_with('-') {
+        args.push("--".to_string());
+    }
+    args.push(value.to_string());
+}
+
+/// Parse bd's stdout as JSON, salvaging payloads from builds that print a
+/// stray log line first: when the full buffer fails to parse, retry from the
+/// first line that starts with `{` or `[`. The original error is returned if
+/// the salvage doesn't parse either.
+fn parse_bd_output(stdout: &[u8]) -> Result<Value, serde_json::Error> {
+    let err = match serde_json::from_slice(stdout) {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    let text = String::from_utf8_lossy(stdout);
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if offset > 0 {
+                if let Ok(value) = serde_json::from_str(&text[offset..]) {
+                    tracing::warn!("bd printed a non-JSON preamble; salvaged the payload");
+                    return Ok(value);
+                }
+            }
+            break;
+        }
+        offset += line.len();
+    }
+    Err(err)
+}
+
+/// Parse bd's `--version` output into a semver. Accepts the bare `0.47.1`
+/// form as well as prefixed ones like `bd version 0.47.1` or `bd v0.47.1`.
+fn parse_bd_version(raw: &str) -> Option<semver::Version> {
+    raw.split_whitespace()
+        .find_map(|word| semver::Version::parse(word.trim_start_matches('v')).ok())
+}
+
+/// Issue and gate IDs are interpolated directly into bd's argument list and
+/// can't be protected with `--` (flags follow them); reject anything that
+/// would be parsed as a flag instead.
+fn validate_id(id: &str) -> BdResult<()> {
+    if id.starts_with('-') {
+        return Err(BdError::InvalidArgument(format!(
+            "ID may not begin with '-': {id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Rewrite bd's terse duplicate/missing-label failures into something worth
+/// showing in a toast; other errors pass through untouched.
+fn friendly_label_error(err: BdError, id: &str, label: &str) -> BdError {
+    let BdError::CommandFailed { stderr } = &err else {
+        return err;
+    };
+    let lower = stderr.to_lowercase();
+    if lower.contains("already") {
+        return BdError::CommandFailed {
+            stderr: format!("label \"{label}\" is already on {id}"),
+        };
+    }
+    if lower.contains("not found") || lower.contains("no such") || lower.contains("not present") {
+        return BdError::CommandFailed {
+            stderr: format!("label \"{label}\" is not on {id}"),
+        };
+    }
+    err
+}
+
+/// bd sometimes returns a bare object, sometimes a one-element array, and
+/// sometimes a `{"issue": {...}}` wrapper depending on version and command.
+fn issue_from_value(value: Value) -> BdResult<Issue> {
+    let value = unwrap_entity(value, "issue");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn issues_from_value(value: Value) -> BdResult<Vec<Issue>> {
+    let value = unwrap_list(value, "issues");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn epics_from_value(value: Value) -> BdResult<Vec<EpicStatus>> {
+    let value = unwrap_list(value, "epics");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn comment_from_value(value: Value) -> BdResult<Comment> {
+    let value = unwrap_entity(value, "comment");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn comments_from_value(value: Value) -> BdResult<Vec<Comment>> {
+    let value = unwrap_list(value, "comments");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn gate_from_value(value: Value) -> BdResult<Gate> {
+    let value = unwrap_entity(value, "gate");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn gates_from_value(value: Value) -> BdResult<Vec<Gate>> {
+    let value = unwrap_list(value, "gates");
+    Ok(serde_json::from_value(value)?)
+}
+
+fn unwrap_entity(value: Value, key: &str) -> Value {
+    match value {
+        Value::Array(mut items) if !items.is_empty() => items.remove(0),
+        Value::Object(mut obj) if obj.contains_key(key) => obj.remove(key).unwrap(),
+        other => other,
+    }
+}
+
+fn unwrap_list(value: Value, key: &str) -> Value {
+    match value {
+        Value::Object(mut obj) if obj.contains_key(key) => obj.remove(key).unwrap(),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_client() -> BdClient {
+        BdClient::with_binary("/usr/bin/true", "/tmp")
+    }
+
+    #[test]
+    fn create_args_include_configured_default_assignee() {
+        let client = test_client();
+        client.set_default_assignee(Some("alice".to_string()));
+        let args = client.build_create_args("Fix the thing", None, &[], None, &[], None, None);
+        let pos = args.iter().position(|a| a == "--assignee").unwrap();
+        assert_eq!(args[pos + 1], "alice");
+    }
+
+    #[test]
+    fn explicit_assignee_overrides_default() {
+        let client = test_client();
+        client.set_default_assignee(Some("alice".to_string()));
+        let args =
+            client.build_create_args("Fix the thing", None, &[], None, &[], Some("bob"), None);
+        let pos = args.iter().position(|a| a == "--assignee").unwrap();
+        assert_eq!(args[pos + 1], "bob");
+        assert_eq!(args.iter().filter(|a| *a == "--assignee").count(), 1);
+    }
+
+    #[test]
+    fn priority_maps_to_flag_and_none_leaves_args_unchanged() {
+        let client = test_client();
+        let with = client.build_create_args("t", None, &[], None, &[], None, Some(2));
+        let pos = with.iter().position(|a| a == "--priority").unwrap();
+        assert_eq!(with[pos + 1], "2");
+
+        let without = client.build_create_args("t", None, &[], None, &[], None, None);
+        assert!(!without.contains(&"--priority".to_string()));
+    }
+
+    #[tokio::test]
+    async fn out_of_range_priority_is_rejected_before_spawning_bd() {
+        let client = test_client();
+        let err = client
+            .create_issue("t", None, &[], None, &[], None, Some(9))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BdError::InvalidArgument(_)));
+        assert!(err.to_string().contains("0 and 4"));
+    }
+
+    #[test]
+    fn flag_lookalike_title_is_kept_positional_behind_a_separator() {
+        let client = test_client();
+        let args = client.build_create_args("--status", None, &[], None, &[], None, None);
+        let pos = args.iter().position(|a| a == "--").unwrap();
+        assert_eq!(args[pos + 1], "--status");
+        assert_eq!(pos + 2, args.len());
+
+        // Plain titles don't grow a separator.
+        let args = client.build_create_args("Fix the thing", None, &[], None, &[], None, None);
+        assert!(!args.contains(&"--".to_string()));
+    }
+
+    #[tokio::test]
+    async fn flag_lookalike_ids_are_rejected() {
+        let client = test_client();
+        let err = client.close_issue("--all").await.unwrap_err();
+        assert!(matches!(err, BdError::InvalidArgument(_)));
+        let err = client.get_gate("-g").await.unwrap_err();
+        assert!(matches!(err, BdError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn no_assignee_flag_without_default_or_explicit() {
+        let client = test_client();
+        let args = client.build_create_args("Fix the thing", None, &[], None, &[], None, None);
+        assert!(!args.contains(&"--assignee".to_string()));
+    }
+
+    #[test]
+    fn version_output_parses_in_its_common_shapes() {
+        let parsed = parse_bd_version("0.47.1");
+        assert_eq!(parsed, Some(semver::Version::new(0, 47, 1)));
+        assert_eq!(
+            parse_bd_version("bd version 0.47.1"),
+            Some(semver::Version::new(0, 47, 1))
+        );
+        assert_eq!(
+            parse_bd_version("bd v1.2.3"),
+            Some(semver::Version::new(1, 2, 3))
+        );
+        assert_eq!(parse_bd_version("no version here"), None);
+        assert_eq!(parse_bd_version(""), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn bd_version_is_probed_once_and_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let count_file = dir.path().join("count");
+        let script = fake_bd(
+            dir.path(),
+            &format!("echo x >> {}\necho 'bd version 0.47.1'", count_file.display()),
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        assert_eq!(
+            client.bd_version().await,
+            Some(semver::Version::new(0, 47, 1))
+        );
+        assert!(client.supports_since_flag().await);
+        let spawns = std::fs::read_to_string(&count_file).unwrap().lines().count();
+        assert_eq!(spawns, 1);
+    }
+
+    #[cfg(unix)]
+    fn fake_bd(dir: &std::path::Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let script = dir.join("bd");
+        std::fs::write(&script, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn identical_reads_within_ttl_spawn_bd_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let count_file = dir.path().join("count");
+        let script = fake_bd(
+            dir.path(),
+            &format!("echo x >> {}\necho '[]'", count_file.display()),
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        client.list_issues().await.unwrap();
+        client.list_issues().await.unwrap();
+
+        let spawns = std::fs::read_to_string(&count_file).unwrap().lines().count();
+        assert_eq!(spawns, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_bust_the_read_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let count_file = dir.path().join("count");
+        let script = fake_bd(
+            dir.path(),
+            &format!(
+                "echo x >> {}\nif [ \"$1\" = list ]; then echo '[]'; else echo '{{\"id\":\"bd-1\",\"title\":\"t\"}}'; fi",
+                count_file.display()
+            ),
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        client.list_issues().await.unwrap();
+        client.close_issue("bd-1").await.unwrap();
+        client.list_issues().await.unwrap();
+
+        let spawns = std::fs::read_to_string(&count_file).unwrap().lines().count();
+        assert_eq!(spawns, 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn init_workspace_runs_in_the_target_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("new-ws");
+        std::fs::create_dir(&target).unwrap();
+        let cwd_file = dir.path().join("cwd");
+        let script = fake_bd(
+            dir.path(),
+            &format!("pwd > {}\necho '{{}}'", cwd_file.display()),
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        client.init_workspace(&target).await.unwrap();
+        let recorded = std::fs::read_to_string(&cwd_file).unwrap();
+        assert_eq!(
+            std::fs::canonicalize(recorded.trim()).unwrap(),
+            std::fs::canonicalize(&target).unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn already_registered_workspace_is_a_usage_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo 'workspace already registered' >&2\nexit 1",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let err = client.init_workspace(dir.path()).await.unwrap_err();
+        assert!(matches!(err, BdError::InvalidArgument(_)));
+        assert!(err.to_string().contains("already registered"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn queued_write_is_cancelled_by_close_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let count_file = dir.path().join("count");
+        let script = fake_bd(
+            dir.path(),
+            &format!(
+                "echo x >> {}\nsleep 0.5\necho '{{\"id\":\"bd-1\",\"title\":\"t\"}}'",
+                count_file.display()
+            ),
+        );
+        let client = Arc::new(BdClient::with_binary(&script, dir.path()));
+
+        let in_flight = {
+            let client = client.clone();
+            tokio::spawn(async move { client.close_issue("bd-1").await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let queued = {
+            let client = client.clone();
+            tokio::spawn(async move { client.close_issue("bd-2").await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        client.close_writes();
+
+        assert!(matches!(queued.await.unwrap(), Err(BdError::Closed)));
+        assert!(in_flight.await.unwrap().is_ok());
+        let spawns = std::fs::read_to_string(&count_file).unwrap().lines().count();
+        assert_eq!(spawns, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn bulk_update_falls_back_to_per_issue_and_reports_partial_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "case \"$2\" in\n\
+             *,*) echo 'comma IDs unsupported' >&2; exit 2;;\n\
+             bd-bad) echo 'no such issue' >&2; exit 1;;\n\
+             esac\n\
+             echo \"{\\\"id\\\":\\\"$2\\\",\\\"title\\\":\\\"t\\\"}\"",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let results = client
+            .bulk_update_status(&["bd-1", "bd-bad", "bd-2"], "closed")
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, "bd-1");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().id, "bd-2");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn bulk_gate_resolve_continues_past_individual_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "case \"$2\" in\n\
+             gate-bad) echo 'no such gate' >&2; exit 1;;\n\
+             *) echo \"{\\\"id\\\":\\\"$2\\\",\\\"status\\\":\\\"approved\\\"}\";;\n\
+             esac",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let results = client
+            .resolve_gates(&["gate-1", "gate-bad", "gate-2"], "lgtm")
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, "gate-1");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().id, "gate-2");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn widened_write_semaphore_lets_writes_overlap() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "sleep 0.3\necho '{\"id\":\"bd-1\",\"title\":\"t\"}'",
+        );
+        let client = Arc::new(BdClient::with_binary(&script, dir.path()));
+        client.set_write_concurrency(2);
+
+        let started = Instant::now();
+        let a = {
+            let client = client.clone();
+            tokio::spawn(async move { client.close_issue("bd-1").await })
+        };
+        let b = {
+            let client = client.clone();
+            tokio::spawn(async move { client.close_issue("bd-2").await })
+        };
+        a.await.unwrap().unwrap();
+        b.await.unwrap().unwrap();
+
+        // Serialized these would take >= 0.6s.
+        assert!(started.elapsed() < Duration::from_millis(550));
+    }
+
+    #[test]
+    fn list_filters_map_to_flags_and_none_matches_plain_list() {
+        assert_eq!(
+            BdClient::build_list_args(None, None, None),
+            vec!["list", "--json"]
+        );
+        assert_eq!(
+            BdClient::build_list_args(Some("open"), Some("alice"), Some("backend")),
+            vec![
+                "list",
+                "--status",
+                "open",
+                "--assignee",
+                "alice",
+                "--label",
+                "backend",
+                "--json"
+            ]
+        );
+    }
+
+    #[test]
+    fn slice_page_clamps_past_the_end() {
+        let issues: Vec<Issue> = (0..5)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "id": format!("bd-{i}"), "title": "t"
+                }))
+                .unwrap()
+            })
+            .collect();
+        let page = BdClient::slice_page(issues.clone(), 3, 10);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.issues.len(), 2);
+        assert_eq!(page.issues[0].id, "bd-3");
+
+        let page = BdClient::slice_page(issues, 10, 10);
+        assert!(page.issues.is_empty());
+        assert_eq!(page.total, 5);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn paged_list_falls_back_to_slicing_when_bd_rejects_the_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "case \"$*\" in *--limit*) echo 'unknown flag: --limit' >&2; exit 2;; esac\n\
+             echo '[{\"id\":\"bd-1\",\"title\":\"a\"},{\"id\":\"bd-2\",\"title\":\"b\"},{\"id\":\"bd-3\",\"title\":\"c\"}]'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let page = client.list_issues_paged(1, 1).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.issues.len(), 1);
+        assert_eq!(page.issues[0].id, "bd-2");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn paged_list_uses_server_total_when_bd_reports_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo '{\"issues\": [{\"id\":\"bd-2\",\"title\":\"b\"}], \"total\": 42}'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let page = client.list_issues_paged(1, 1).await.unwrap();
+        assert_eq!(page.total, 42);
+        assert_eq!(page.issues.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn progress_lines_reach_the_callback_and_stdout_still_parses() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo '{\"progress\": 0.5, \"message\": \"halfway\"}' >&2\n\
+             echo 'plain warning' >&2\n\
+             echo '[]'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+        let (tx, mut rx) = mpsc::channel(16);
+
+        let value = client
+            .run_bd_json_with_progress(&["list", "--json"], tx)
+            .await
+            .unwrap();
+        assert_eq!(value, serde_json::json!([]));
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.progress, Some(0.5));
+        assert_eq!(first.message, "halfway");
+        let second = rx.recv().await.unwrap();
+        assert_eq!(second.progress, None);
+        assert_eq!(second.message, "plain warning");
+    }
+
+    #[test]
+    fn resolve_gate_records_acting_user_when_configured() {
+        let client = test_client();
+        let args = client.build_resolve_gate_args("gate-1", "lgtm");
+        assert!(!args.contains(&"--by".to_string()));
+
+        client.set_current_user(Some("alice".to_string()));
+        let args = client.build_resolve_gate_args("gate-1", "lgtm");
+        let pos = args.iter().position(|a| a == "--by").unwrap();
+        assert_eq!(args[pos + 1], "alice");
+    }
+
+    #[test]
+    fn daemon_socket_path_sits_inside_the_workspace() {
+        let client = test_client();
+        let socket = client.socket_path();
+        assert!(socket.starts_with(client.workspace()));
+        assert!(socket.ends_with(".beads/bd.sock"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_existence_tracks_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = BdClient::with_binary("/usr/bin/true", dir.path());
+        assert!(!client.socket_exists());
+        std::fs::create_dir_all(dir.path().join(".beads")).unwrap();
+        std::fs::write(client.socket_path(), b"").unwrap();
+        assert!(client.socket_exists());
+    }
+
+    #[test]
+    fn json_after_a_log_preamble_still_parses() {
+        let raw = b"WARN: deprecated flag --all\n[{\"id\": \"bd-1\", \"title\": \"t\"}]";
+        let value = parse_bd_output(raw).unwrap();
+        assert_eq!(value[0]["id"], "bd-1");
+
+        // Clean output takes the normal path.
+        assert_eq!(parse_bd_output(b"{\"ok\": true}").unwrap()["ok"], true);
+
+        // A preamble with no JSON after it still errors.
+        assert!(parse_bd_output(b"WARN: one\nWARN: two\n").is_err());
+        // And garbage that merely starts with a brace isn't salvaged.
+        assert!(parse_bd_output(b"WARN: x\n{not json").is_err());
+    }
+
+    #[test]
+    fn timeout_is_selected_by_the_leading_subcommand() {
+        let client = test_client();
+        assert_eq!(client.timeout_for(&["list", "--json"]), LONG_TIMEOUT);
+        assert_eq!(client.timeout_for(&["ready", "--json"]), LONG_TIMEOUT);
+        assert_eq!(
+            client.timeout_for(&["daemon", "status", "--json"]),
+            SHORT_TIMEOUT
+        );
+        assert_eq!(client.timeout_for(&["show", "bd-1", "--json"]), DEFAULT_TIMEOUT);
+        assert_eq!(client.timeout_for(&[]), DEFAULT_TIMEOUT);
+    }
+
+    #[test]
+    fn every_error_variant_has_a_stable_code() {
+        let cases: Vec<(BdError, &str)> = vec![
+            (
+                BdError::CliNotFound {
+                    checked_paths: vec![],
+                },
+                "cli_not_found",
+            ),
+            (
+                BdError::CommandFailed {
+                    stderr: "boom".into(),
+                },
+                "command_failed",
+            ),
+            (
+                BdError::ParseError(serde_json::from_str::<Value>("not json").unwrap_err()),
+                "parse_error",
+            ),
+            (BdError::Timeout(Duration::from_secs(1)), "timeout"),
+            (BdError::InvalidArgument("bad".into()), "invalid_argument"),
+            (BdError::Closed, "closed"),
+            (
+                BdError::Io(std::io::Error::other("io")),
+                "io_error",
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.code(), code, "{err}");
+        }
+    }
+
+    #[test]
+    fn reject_gate_args_mirror_the_resolve_shape() {
+        let client = test_client();
+        let args = client.build_reject_gate_args("gate-1", "needs rework");
+        assert_eq!(args[0], "reject-gate");
+        assert!(!args.contains(&"--by".to_string()));
+
+        client.set_current_user(Some("alice".to_string()));
+        let args = client.build_reject_gate_args("gate-1", "needs rework");
+        let pos = args.iter().position(|a| a == "--by").unwrap();
+        assert_eq!(args[pos + 1], "alice");
+    }
+
+    #[test]
+    fn epics_parse_from_bare_array_and_wrapped_object() {
+        let bare = serde_json::json!([
+            {"epic_id": "bd-e", "title": "Epic", "total_issues": 4, "closed_issues": 1}
+        ]);
+        let epics = epics_from_value(bare).unwrap();
+        assert_eq!(epics.len(), 1);
+        assert_eq!(epics[0].epic_id, "bd-e");
+        assert_eq!(epics[0].total_issues, 4);
+
+        let wrapped = serde_json::json!({"epics": [{"epic_id": "bd-e", "title": "Epic"}]});
+        let epics = epics_from_value(wrapped).unwrap();
+        assert_eq!(epics.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn list_epics_populates_refresh() {
+        use crate::bd::cache::BeadsCache;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo '{\"epics\": [{\"epic_id\": \"bd-e\", \"title\": \"Epic\"}]}'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let epics = client.list_epics().await.unwrap();
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(vec![], vec![], epics);
+        assert!(cache.get_epic_status("bd-e").is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn success_stderr_is_captured_without_breaking_the_parse() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo 'warning: --foo is deprecated' >&2\necho '[]'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let (value, stderr) = client
+            .run_bd_json_with_stderr(&["list", "--json"])
+            .await
+            .unwrap();
+        assert_eq!(value, serde_json::json!([]));
+        assert_eq!(stderr.as_deref(), Some("warning: --foo is deprecated"));
+
+        // The plain wrapper drops it but still succeeds.
+        assert!(client.list_issues().await.is_ok());
+    }
+
+    #[test]
+    fn label_errors_are_rewritten_for_display() {
+        let err = friendly_label_error(
+            BdError::CommandFailed {
+                stderr: "error: label already exists on issue".to_string(),
+            },
+            "bd-1",
+            "backend",
+        );
+        assert_eq!(
+            err.to_string(),
+            "bd command failed: label \"backend\" is already on bd-1"
+        );
+
+        let err = friendly_label_error(
+            BdError::CommandFailed {
+                stderr: "error: label not found".to_string(),
+            },
+            "bd-1",
+            "backend",
+        );
+        assert_eq!(
+            err.to_string(),
+            "bd command failed: label \"backend\" is not on bd-1"
+        );
+
+        // Unrelated failures pass through untouched.
+        let err = friendly_label_error(
+            BdError::CommandFailed {
+                stderr: "daemon unreachable".to_string(),
+            },
+            "bd-1",
+            "backend",
+        );
+        assert_eq!(err.to_string(), "bd command failed: daemon unreachable");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unassign_returns_null_assignee_falling_back_to_owner() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "echo '{\"id\":\"bd-1\",\"title\":\"t\",\"assignee\":null,\"owner\":\"alice\"}'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let issue = client.unassign_issue("bd-1").await.unwrap();
+        assert_eq!(issue.assignee, None);
+        assert_eq!(issue.effective_assignee(), Some("alice"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn get_gate_falls_back_to_the_list_when_show_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "if [ \"$2\" = show ]; then echo 'unknown subcommand' >&2; exit 2; fi\n\
+             echo '{\"gates\": [{\"id\": \"gate-1\", \"status\": \"approved\"}]}'",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let gate = client.get_gate("gate-1").await.unwrap();
+        assert_eq!(gate.status, "approved");
+        assert!(client.get_gate("gate-404").await.is_err());
+    }
+
+    #[test]
+    fn comments_parse_from_bare_array_and_wrapped_object() {
+        let bare = serde_json::json!([
+            {"id": "c-1", "author": "alice", "body": "looks good"}
+        ]);
+        let comments = comments_from_value(bare).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "looks good");
+
+        let wrapped = serde_json::json!({"comments": [{"id": "c-1", "body": "hi"}]});
+        assert_eq!(comments_from_value(wrapped).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unwrap_entity_handles_all_shapes() {
+        let bare = serde_json::json!({"id": "bd-1"});
+        assert_eq!(unwrap_entity(bare.clone(), "issue"), bare);
+        let arr = serde_json::json!([{"id": "bd-1"}]);
+        assert_eq!(unwrap_entity(arr, "issue"), bare);
+        let wrapped = serde_json::json!({"issue": {"id": "bd-1"}});
+        assert_eq!(unwrap_entity(wrapped, "issue"), bare);
+    }
+}
@@ -0,0 +1,187 @@
+//! Control of the bd background daemon.
+//!
+//! The daemon keeps the activity stream and workspace index alive; the
+//! dashboard needs to be able to check on it and nudge it without asking
+//! the user to drop to a terminal.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::process::Command;
+
+use super::client::{find_bd_binary, BdError, BdResult, BD_EXE};
+
+/// Daemon commands get a shorter leash than regular reads: they either
+/// answer immediately or the daemon is wedged.
+const DAEMON_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bound on how long a restart waits for the old daemon's socket to
+/// disappear before starting the replacement anyway.
+const DAEMON_START_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the socket wait re-checks the file.
+const SOCKET_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Handle for the bd daemon of one workspace. Shares
+/// [`find_bd_binary`]'s platform-aware discovery (`bd` on unix, `bd.exe`
+/// under `%LOCALAPPDATA%` or the profile dir on Windows) so the two never
+/// disagree about which binary is in play.
+pub struct DaemonManager {
+    bd_path: PathBuf,
+    workspace: PathBuf,
+}
+
+impl DaemonManager {
+    pub fn new(workspace: impl Into<PathBuf>) -> BdResult<Self> {
+        let bd_path = find_bd_binary()
+            .map_err(|checked_paths| BdError::CliNotFound { checked_paths })?;
+        Ok(Self::with_binary(bd_path, workspace))
+    }
+
+    pub fn with_binary(bd_path: impl Into<PathBuf>, workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            bd_path: bd_path.into(),
+            workspace: workspace.into(),
+        }
+    }
+
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    /// Raw `bd daemon status --json`; `Err` when the daemon (or bd itself)
+    /// doesn't answer.
+    pub async fn status(&self) -> BdResult<Value> {
+        self.run_daemon(&["daemon", "status", "--json"]).await
+    }
+
+    /// Start the daemon if it isn't already running. bd treats a redundant
+    /// start as a no-op, so this is safe to call on app launch.
+    pub async fn start(&self) -> BdResult<Value> {
+        self.run_daemon(&["daemon", "start", "--json"]).await
+    }
+
+    /// Alias for [`start`](Self::start) that reads better at call sites
+    /// that only care about the end state, not the transition.
+    pub async fn ensure_running(&self) -> BdResult<Value> {
+        self.start().await
+    }
+
+    /// Ask the daemon to shut down, then wait for its socket to disappear.
+    /// `bd daemon stop` returns before the process has fully exited, so
+    /// without the wait an immediate [`ensure_running`](Self::ensure_running)
+    /// races the dying daemon for the socket. Errors if the socket is still
+    /// present after [`DAEMON_START_TIMEOUT`].
+    pub async fn stop(&self) -> BdResult<Value> {
+        let started = tokio::time::Instant::now();
+        let output = self.run_daemon(&["daemon", "stop", "--json"]).await?;
+        if !wait_for_socket_removal(&self.socket_path(), DAEMON_START_TIMEOUT).await {
+            return Err(BdError::Timeout(DAEMON_START_TIMEOUT));
+        }
+        tracing::debug!("daemon terminated in {}ms", started.elapsed().as_millis());
+        Ok(output)
+    }
+
+    /// The daemon's unix socket inside the workspace — its presence is the
+    /// cheapest "is the old process really gone" signal we have.
+    pub fn socket_path(&self) -> PathBuf {
+        self.workspace.join(".beads").join("bd.sock")
+    }
+
+    /// Stop-then-start for a wedged daemon. [`stop`](Self::stop) waits for
+    /// the old daemon's socket to vanish, so the replacement never races the
+    /// dying process. A daemon that was never running makes the stop fail
+    /// and leaves no socket; we log and start it anyway.
+    pub async fn restart(&self) -> BdResult<Value> {
+        if let Err(err) = self.stop().await {
+            tracing::debug!("daemon stop before restart failed (likely not running): {err}");
+        }
+        self.ensure_running().await
+    }
+
+    async fn run_daemon(&self, args: &[&str]) -> BdResult<Value> {
+        let output = tokio::time::timeout(
+            DAEMON_TIMEOUT,
+            Command::new(&self.bd_path)
+                .args(args)
+                .current_dir(&self.workspace)
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .map_err(|_| BdError::Timeout(DAEMON_TIMEOUT))??;
+
+        if !output.status.success() {
+            return Err(BdError::CommandFailed {
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+/// Poll until `socket` no longer exists or `timeout` elapses. Returns
+/// whether the socket actually went away; on timeout the caller starts the
+/// daemon anyway and lets bd deal with the stale socket.
+async fn wait_for_socket_removal(socket: &Path, timeout: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    while socket.exists() {
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(SOCKET_POLL_INTERVAL).await;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn socket_wait_returns_once_the_file_is_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let sock = dir.path().join("bd.sock");
+        std::fs::write(&sock, b"").unwrap();
+
+        let waiter = wait_for_socket_removal(&sock, Duration::from_secs(5));
+        let remover = async {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            std::fs::remove_file(&sock).unwrap();
+        };
+        let (gone, ()) = tokio::join!(waiter, remover);
+        assert!(gone);
+    }
+
+    #[tokio::test]
+    async fn socket_wait_gives_up_after_the_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let sock = dir.path().join("bd.sock");
+        std::fs::write(&sock, b"").unwrap();
+
+        assert!(!wait_for_socket_removal(&sock, Duration::from_millis(250)).await);
+        assert!(sock.exists());
+    }
+
+    #[tokio::test]
+    async fn missing_socket_needs_no_wait() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(wait_for_socket_removal(&dir.path().join("bd.sock"), Duration::ZERO).await);
+    }
+
+    #[test]
+    fn socket_path_lives_under_dot_beads() {
+        let manager = DaemonManager::with_binary("/usr/bin/bd", "/tmp/ws");
+        assert_eq!(manager.socket_path(), Path::new("/tmp/ws/.beads/bd.sock"));
+    }
+
+    #[test]
+    fn exe_name_matches_platform() {
+        if cfg!(windows) {
+            assert_eq!(BD_EXE, "bd.exe");
+        } else {
+            assert_eq!(BD_EXE, "bd");
+        }
+    }
+}
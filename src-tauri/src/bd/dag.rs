@@ -0,0 +1,1367 @@
+//! Dependency DAG construction for the epic graph view.
+//!
+//! [`DagBuilder`] turns the cached issue/gate maps into a [`DagGraph`] the
+//! frontend can render directly; all blocking/edge semantics live here so the
+//! UI never re-derives them.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::status::{status_is_closed, IssueStatus};
+use super::types::{Gate, Issue};
+
+/// Resolve a possibly short-form dependency ID ("abc.1") against an issue
+/// map keyed by fully-qualified IDs ("my-project-abc.1"). Direct hits win;
+/// otherwise an unambiguous `-<short>` suffix match is accepted. Shared by
+/// everything that follows dependency references so short and full forms
+/// link up consistently.
+pub(crate) fn resolve_issue_id(id: &str, issues: &HashMap<String, Issue>) -> Option<String> {
+    if issues.contains_key(id) {
+        return Some(id.to_string());
+    }
+    let suffix = format!("-{id}");
+    let mut matches = issues.keys().filter(|key| key.ends_with(&suffix));
+    match (matches.next(), matches.next()) {
+        (Some(key), None) => Some(key.clone()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeType {
+    Blocks,
+    RelatesTo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DagNode {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    /// `"issue"`, `"epic"`, or `"gate"`.
+    pub node_type: String,
+    /// IDs of unresolved dependencies still blocking this node.
+    pub blocked_by: Vec<String>,
+    /// Topological depth for left-to-right layout: roots are 0, every other
+    /// node sits one past its deepest predecessor. Nodes caught in a
+    /// dependency cycle get [`usize::MAX`] as a sentinel. Defaulted so
+    /// graphs serialized before this field existed still deserialize.
+    #[serde(default)]
+    pub layer: usize,
+    /// How many edges point at this node, so tooltips don't recount edges.
+    /// Defaulted like `layer` for pre-existing serialized graphs.
+    #[serde(default)]
+    pub in_degree: usize,
+    /// How many edges lead out of this node.
+    #[serde(default)]
+    pub out_degree: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DagEdge {
+    pub from: String,
+    pub to: String,
+    pub edge_type: EdgeType,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DagGraph {
+    pub nodes: Vec<DagNode>,
+    pub edges: Vec<DagEdge>,
+    /// Header numbers for the UI; populated by `build_dag`.
+    #[serde(default)]
+    pub summary: Option<DagSummary>,
+}
+
+/// Aggregate metrics over one built graph, so the UI doesn't have to walk
+/// nodes and edges client-side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DagSummary {
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Length (in nodes) of the longest dependency chain.
+    pub max_depth: usize,
+    pub blocked_nodes: usize,
+    pub pending_gates: usize,
+    pub has_cycle: bool,
+}
+
+impl DagGraph {
+    /// Compute the summary metrics for this graph.
+    pub fn compute_summary(&self) -> DagSummary {
+        let (max_depth, has_cycle) = self.longest_chain();
+        DagSummary {
+            node_count: self.nodes.len(),
+            edge_count: self.edges.len(),
+            max_depth,
+            blocked_nodes: self
+                .nodes
+                .iter()
+                .filter(|n| !n.blocked_by.is_empty())
+                .count(),
+            pending_gates: self
+                .nodes
+                .iter()
+                .filter(|n| n.node_type == "gate" && n.status == "pending")
+                .count(),
+            has_cycle,
+        }
+    }
+
+    /// Every dependency cycle in the graph, as lists of the node IDs
+    /// involved. Plain DFS with a gray/black coloring; each cycle is
+    /// reported once, from wherever the walk first entered it.
+    pub fn detect_cycles(&self) -> Vec<Vec<String>> {
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            children
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge.to.as_str());
+        }
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum Color {
+            Gray,
+            Black,
+        }
+
+        fn dfs<'a>(
+            node: &'a str,
+            children: &HashMap<&'a str, Vec<&'a str>>,
+            color: &mut HashMap<&'a str, Color>,
+            path: &mut Vec<&'a str>,
+            cycles: &mut Vec<Vec<String>>,
+        ) {
+            color.insert(node, Color::Gray);
+            path.push(node);
+            for kid in children.get(node).into_iter().flatten() {
+                match color.get(kid) {
+                    Some(Color::Gray) => {
+                        if let Some(pos) = path.iter().position(|n| n == kid) {
+                            cycles.push(path[pos..].iter().map(|n| n.to_string()).collect());
+                        }
+                    }
+                    Some(Color::Black) => {}
+                    None => dfs(kid, children, color, path, cycles),
+                }
+            }
+            path.pop();
+            color.insert(node, Color::Black);
+        }
+
+        let mut color = HashMap::new();
+        let mut path = Vec::new();
+        let mut cycles = Vec::new();
+        for node in &self.nodes {
+            if !color.contains_key(node.id.as_str()) {
+                dfs(node.id.as_str(), &children, &mut color, &mut path, &mut cycles);
+            }
+        }
+        cycles
+    }
+
+    /// Render as Graphviz DOT for external tooling: nodes labeled by title
+    /// and colored by type, solid edges for `Blocks`, dashed for
+    /// `RelatesTo`.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph epic {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let fillcolor = match node.node_type.as_str() {
+                "epic" => "lightgoldenrod",
+                "gate" => "lightpink",
+                _ => "lightblue",
+            };
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\", style=filled, fillcolor={fillcolor}];\n",
+                escape_dot(&node.id),
+                escape_dot(&node.title),
+            ));
+        }
+        for edge in &self.edges {
+            let style = match edge.edge_type {
+                EdgeType::Blocks => "solid",
+                EdgeType::RelatesTo => "dashed",
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [style={style}];\n",
+                escape_dot(&edge.from),
+                escape_dot(&edge.to),
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render as a Mermaid `flowchart TD` block suitable for pasting into
+    /// Markdown. Raw bd IDs contain dots and dashes that Mermaid chokes on,
+    /// so node IDs are remapped via [`mermaid_id`]; statuses are expressed
+    /// as CSS classes keyed by status category.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+        if self.nodes.is_empty() {
+            out.push_str("  empty[\"no nodes\"]\n");
+            return out;
+        }
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "  {}[\"{}\"]\n",
+                mermaid_id(&node.id),
+                escape_mermaid(&node.title),
+            ));
+        }
+        for edge in &self.edges {
+            let arrow = match edge.edge_type {
+                EdgeType::Blocks => "-->",
+                EdgeType::RelatesTo => "-.->",
+            };
+            out.push_str(&format!(
+                "  {} {arrow} {}\n",
+                mermaid_id(&edge.from),
+                mermaid_id(&edge.to),
+            ));
+        }
+        out.push_str("  classDef open fill:#e3f2fd,stroke:#1565c0\n");
+        out.push_str("  classDef in_progress fill:#fff8e1,stroke:#f9a825\n");
+        out.push_str("  classDef blocked fill:#ffebee,stroke:#c62828\n");
+        out.push_str("  classDef closed fill:#e8f5e9,stroke:#2e7d32\n");
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "  class {} {}\n",
+                mermaid_id(&node.id),
+                IssueStatus::from_raw(&node.status).category(),
+            ));
+        }
+        out
+    }
+
+    /// Keep only nodes whose status matches `statuses` (case-insensitive),
+    /// collapsing paths through dropped nodes into direct edges: if B is
+    /// removed from A→B→C, a synthesized A→C keeps the chain connected.
+    fn retain_statuses(&mut self, statuses: &[String]) {
+        let wanted: std::collections::HashSet<String> =
+            statuses.iter().map(|s| s.to_lowercase()).collect();
+        let keep: std::collections::HashSet<String> = self
+            .nodes
+            .iter()
+            .filter(|n| wanted.contains(&n.status.to_lowercase()))
+            .map(|n| n.id.clone())
+            .collect();
+
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            children
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge.to.as_str());
+        }
+
+        let mut new_edges: Vec<DagEdge> = self
+            .edges
+            .iter()
+            .filter(|e| keep.contains(&e.from) && keep.contains(&e.to))
+            .cloned()
+            .collect();
+
+        for from in &keep {
+            // Walk through dropped nodes only; the first kept node on each
+            // branch becomes a direct successor.
+            let mut stack: Vec<&str> = children.get(from.as_str()).cloned().unwrap_or_default();
+            let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+            while let Some(next) = stack.pop() {
+                if !visited.insert(next) {
+                    continue;
+                }
+                if keep.contains(next) {
+                    let exists = new_edges
+                        .iter()
+                        .any(|e| e.from == *from && e.to == next);
+                    if !exists {
+                        new_edges.push(DagEdge {
+                            from: from.clone(),
+                            to: next.to_string(),
+                            edge_type: EdgeType::Blocks,
+                        });
+                    }
+                } else if let Some(grandchildren) = children.get(next) {
+                    stack.extend(grandchildren);
+                }
+            }
+        }
+
+        self.nodes.retain(|n| keep.contains(&n.id));
+        self.edges = new_edges;
+    }
+
+    /// Fraction of the epic's work that is done, 0.0–1.0. Only plain issue
+    /// nodes count: gates aren't work and the epic node would skew its own
+    /// denominator. An epic with no issue nodes reports 0.0.
+    pub fn progress(&self) -> f32 {
+        let work: Vec<&DagNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == "issue")
+            .collect();
+        if work.is_empty() {
+            return 0.0;
+        }
+        let closed = work
+            .iter()
+            .filter(|n| status_is_closed(&n.status))
+            .count();
+        closed as f32 / work.len() as f32
+    }
+
+    /// The longest dependency chain from any root to any leaf, as ordered
+    /// node IDs with every node counting as unit weight. Ties are broken
+    /// toward the lexicographically smallest path so the result is stable
+    /// across runs. Cycle members are excluded the same way layering
+    /// excludes them; an all-cyclic graph yields an empty path.
+    pub fn critical_path(&self) -> Vec<String> {
+        let cyclic: std::collections::HashSet<String> =
+            self.detect_cycles().into_iter().flatten().collect();
+
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut has_parent: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        for edge in &self.edges {
+            if cyclic.contains(&edge.from) || cyclic.contains(&edge.to) {
+                continue;
+            }
+            children
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge.to.as_str());
+            has_parent.insert(edge.to.as_str());
+        }
+
+        fn path_from(
+            id: &str,
+            children: &HashMap<&str, Vec<&str>>,
+            memo: &mut HashMap<String, Vec<String>>,
+        ) -> Vec<String> {
+            if let Some(path) = memo.get(id) {
+                return path.clone();
+            }
+            let mut best: Vec<String> = Vec::new();
+            let mut kids = children.get(id).cloned().unwrap_or_default();
+            kids.sort_unstable();
+            kids.dedup();
+            for kid in kids {
+                // Ascending order plus strictly-longer acceptance keeps the
+                // lexicographically smallest path among equals.
+                let candidate = path_from(kid, children, memo);
+                if candidate.len() > best.len() {
+                    best = candidate;
+                }
+            }
+            let mut path = vec![id.to_string()];
+            path.extend(best);
+            memo.insert(id.to_string(), path.clone());
+            path
+        }
+
+        let mut roots: Vec<&str> = self
+            .nodes
+            .iter()
+            .map(|n| n.id.as_str())
+            .filter(|id| !cyclic.contains(*id) && !has_parent.contains(id))
+            .collect();
+        roots.sort_unstable();
+
+        let mut memo = HashMap::new();
+        let mut best: Vec<String> = Vec::new();
+        for root in roots {
+            let candidate = path_from(root, &children, &mut memo);
+            if candidate.len() > best.len() {
+                best = candidate;
+            }
+        }
+        best
+    }
+
+    /// Assign each node its topological layer (longest path from the
+    /// roots). Cycle members keep the sentinel layer and are logged; edges
+    /// touching them are ignored so the rest of the graph still layers
+    /// sensibly.
+    fn assign_layers(&mut self) {
+        use std::collections::HashSet;
+
+        let cyclic: HashSet<String> = self.detect_cycles().into_iter().flatten().collect();
+        if !cyclic.is_empty() {
+            tracing::warn!("cycle members get sentinel layer: {cyclic:?}");
+        }
+
+        let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            if cyclic.contains(&edge.from) || cyclic.contains(&edge.to) {
+                continue;
+            }
+            parents
+                .entry(edge.to.as_str())
+                .or_default()
+                .push(edge.from.as_str());
+        }
+
+        fn layer_of<'a>(
+            node: &'a str,
+            parents: &HashMap<&'a str, Vec<&'a str>>,
+            memo: &mut HashMap<&'a str, usize>,
+        ) -> usize {
+            if let Some(layer) = memo.get(node) {
+                return *layer;
+            }
+            let layer = parents
+                .get(node)
+                .map(|ps| {
+                    ps.iter()
+                        .map(|p| layer_of(p, parents, memo))
+                        .max()
+                        .map(|deepest| deepest + 1)
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0);
+            memo.insert(node, layer);
+            layer
+        }
+
+        let mut memo = HashMap::new();
+        let layers: Vec<usize> = self
+            .nodes
+            .iter()
+            .map(|n| {
+                if cyclic.contains(&n.id) {
+                    usize::MAX
+                } else {
+                    layer_of(n.id.as_str(), &parents, &mut memo)
+                }
+            })
+            .collect();
+        for (node, layer) in self.nodes.iter_mut().zip(layers) {
+            node.layer = layer;
+        }
+    }
+
+    /// Count each node's incoming and outgoing edges. Runs after the edge
+    /// set is final (including any status-filter bridging) so the degrees
+    /// describe what the frontend actually draws.
+    fn assign_degrees(&mut self) {
+        let mut in_degree: HashMap<String, usize> = HashMap::new();
+        let mut out_degree: HashMap<String, usize> = HashMap::new();
+        for edge in &self.edges {
+            *out_degree.entry(edge.from.clone()).or_default() += 1;
+            *in_degree.entry(edge.to.clone()).or_default() += 1;
+        }
+        for node in &mut self.nodes {
+            node.in_degree = in_degree.get(&node.id).copied().unwrap_or(0);
+            node.out_degree = out_degree.get(&node.id).copied().unwrap_or(0);
+        }
+    }
+
+    /// Longest path length in nodes, plus whether a cycle was hit while
+    /// walking. Cycle back-edges contribute nothing to depth so the result
+    /// stays finite.
+    fn longest_chain(&self) -> (usize, bool) {
+        use std::collections::HashMap;
+
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            children
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge.to.as_str());
+        }
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            InProgress,
+            Done(usize),
+        }
+
+        fn depth_of<'a>(
+            node: &'a str,
+            children: &HashMap<&'a str, Vec<&'a str>>,
+            marks: &mut HashMap<&'a str, Mark>,
+            has_cycle: &mut bool,
+        ) -> usize {
+            match marks.get(node) {
+                Some(Mark::Done(depth)) => return *depth,
+                Some(Mark::InProgress) => {
+                    *has_cycle = true;
+                    return 0;
+                }
+                None => {}
+            }
+            marks.insert(node, Mark::InProgress);
+            let below = children
+                .get(node)
+                .map(|kids| {
+                    kids.iter()
+                        .map(|kid| depth_of(kid, children, marks, has_cycle))
+                        .max()
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0);
+            marks.insert(node, Mark::Done(below + 1));
+            below + 1
+        }
+
+        let mut marks = HashMap::new();
+        let mut has_cycle = false;
+        let max_depth = self
+            .nodes
+            .iter()
+            .map(|n| depth_of(n.id.as_str(), &children, &mut marks, &mut has_cycle))
+            .max()
+            .unwrap_or(0);
+        (max_depth, has_cycle)
+    }
+}
+
+pub struct DagBuilder<'a> {
+    issues: &'a HashMap<String, Issue>,
+    gates: &'a [Gate],
+}
+
+/// Epic membership: an explicit `parent` pointer or a hierarchical ID under
+/// the epic (`<epic_id>.<n>`).
+pub(crate) fn is_issue_in_epic(issue: &Issue, epic_id: &str) -> bool {
+    if let Some(parent) = issue.extra.get("parent").and_then(|v| v.as_str()) {
+        if parent == epic_id {
+            return true;
+        }
+    }
+    issue.id.starts_with(&format!("{epic_id}."))
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier.
+fn escape_dot(raw: &str) -> String {
+    raw.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Remap a bd ID into an identifier Mermaid accepts: alphanumerics and
+/// underscores only, with an `n_` prefix so IDs never start with a digit.
+fn mermaid_id(raw: &str) -> String {
+    let sanitized: String = raw
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("n_{sanitized}")
+}
+
+/// Escape a title for use inside a quoted Mermaid node label.
+fn escape_mermaid(raw: &str) -> String {
+    raw.replace('"', "#quot;").replace('\n', " ")
+}
+
+impl<'a> DagBuilder<'a> {
+    pub fn new(issues: &'a HashMap<String, Issue>, gates: &'a [Gate]) -> Self {
+        Self { issues, gates }
+    }
+
+    /// Build the dependency graph for one epic from the cached data. With a
+    /// `status_filter`, only nodes whose status matches (case-insensitive)
+    /// survive, and edges through dropped nodes are collapsed into direct
+    /// ones so chains stay connected.
+    pub fn build_dag(&self, epic_id: &str, status_filter: Option<&[String]>) -> DagGraph {
+        let mut graph = DagGraph::default();
+
+        let epic_issues: Vec<&Issue> = self
+            .issues
+            .values()
+            .filter(|issue| {
+                is_issue_in_epic(issue, epic_id)
+                    || (issue.issue_type.as_deref() == Some("Epic") && issue.id == epic_id)
+            })
+            .collect();
+
+        for issue in &epic_issues {
+            let node_type = if issue.issue_type.as_deref() == Some("Epic") {
+                "epic"
+            } else {
+                "issue"
+            };
+            graph.nodes.push(DagNode {
+                id: issue.id.clone(),
+                title: issue.title.clone(),
+                status: issue.status.clone(),
+                node_type: node_type.to_string(),
+                blocked_by: self.blocked_by(issue),
+                layer: 0,
+                in_degree: 0,
+                out_degree: 0,
+            });
+
+            for dep in issue.dependencies_detailed() {
+                let from = resolve_issue_id(&dep.id, self.issues).unwrap_or(dep.id);
+                graph.edges.push(DagEdge {
+                    from,
+                    to: issue.id.clone(),
+                    edge_type: EdgeType::Blocks,
+                });
+            }
+        }
+
+        // `bd show` also reports the reverse direction (`dependents`), which
+        // issues fetched via `get_issue` carry even when the dependent's own
+        // `dependencies` field was never loaded. Synthesize those edges too,
+        // skipping any pair already present from the forward walk.
+        for issue in &epic_issues {
+            for dependent in issue.dependent_ids() {
+                let to = resolve_issue_id(&dependent, self.issues).unwrap_or(dependent);
+                let exists = graph
+                    .edges
+                    .iter()
+                    .any(|e| e.from == issue.id && e.to == to);
+                if !exists {
+                    graph.edges.push(DagEdge {
+                        from: issue.id.clone(),
+                        to,
+                        edge_type: EdgeType::Blocks,
+                    });
+                }
+            }
+        }
+
+        // Non-blocking relations become soft `RelatesTo` edges, but only
+        // between issues that are both in the graph, and never on top of a
+        // pair already linked by a dependency edge in either direction —
+        // blocking semantics win when bd reports both.
+        for issue in &epic_issues {
+            for related in issue.related_ids() {
+                let other = resolve_issue_id(&related, self.issues).unwrap_or(related);
+                let in_graph = epic_issues.iter().any(|i| i.id == other);
+                let linked = graph.edges.iter().any(|e| {
+                    (e.from == issue.id && e.to == other)
+                        || (e.from == other && e.to == issue.id)
+                });
+                if in_graph && !linked {
+                    graph.edges.push(DagEdge {
+                        from: issue.id.clone(),
+                        to: other,
+                        edge_type: EdgeType::RelatesTo,
+                    });
+                }
+            }
+        }
+
+        // Gates are only included when their issue is an actual member of
+        // this epic; a gate on an unknown or out-of-epic issue is skipped
+        // rather than guessed at.
+        for gate in self.gates {
+            let in_epic = epic_issues
+                .iter()
+                .any(|issue| issue.id == gate.issue_id);
+            if in_epic {
+                graph.nodes.push(DagNode {
+                    id: gate.id.clone(),
+                    title: gate.reason.clone().unwrap_or_else(|| "Gate".to_string()),
+                    status: gate.status.clone(),
+                    node_type: "gate".to_string(),
+                    blocked_by: Vec::new(),
+                    layer: 0,
+                    in_degree: 0,
+                    out_degree: 0,
+                });
+                graph.edges.push(DagEdge {
+                    from: gate.id.clone(),
+                    to: gate.issue_id.clone(),
+                    edge_type: EdgeType::Blocks,
+                });
+            }
+        }
+
+        if let Some(statuses) = status_filter {
+            graph.retain_statuses(statuses);
+        }
+
+        graph.assign_layers();
+        graph.assign_degrees();
+        let summary = graph.compute_summary();
+        if summary.has_cycle {
+            tracing::warn!(
+                "dependency cycles in epic {epic_id}: {:?}",
+                graph.detect_cycles()
+            );
+        }
+        graph.summary = Some(summary);
+        graph
+    }
+
+    /// Subgraph centered on one issue: BFS both dependency directions up to
+    /// `depth` hops across the whole cache (no epic scoping), returning the
+    /// reachable issues and the edges connecting them. Gates are left out —
+    /// this view is for debugging dependency chains. An unknown `issue_id`
+    /// yields an empty graph.
+    pub fn build_neighborhood(&self, issue_id: &str, depth: usize) -> DagGraph {
+        let mut graph = DagGraph::default();
+        let Some(origin) = resolve_issue_id(issue_id, self.issues) else {
+            return graph;
+        };
+
+        // Directed dependency edges across the whole cache, both from each
+        // issue's `dependencies` and from `bd show`-style `dependents`.
+        let mut directed: Vec<(String, String)> = Vec::new();
+        for issue in self.issues.values() {
+            for dep in issue.dependency_ids() {
+                let from = resolve_issue_id(&dep, self.issues).unwrap_or(dep);
+                directed.push((from, issue.id.clone()));
+            }
+            for dependent in issue.dependent_ids() {
+                let to = resolve_issue_id(&dependent, self.issues).unwrap_or(dependent);
+                directed.push((issue.id.clone(), to));
+            }
+        }
+        directed.sort_unstable();
+        directed.dedup();
+
+        let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (from, to) in &directed {
+            neighbors.entry(from.as_str()).or_default().push(to.as_str());
+            neighbors.entry(to.as_str()).or_default().push(from.as_str());
+        }
+
+        let mut reached: std::collections::HashSet<String> =
+            std::collections::HashSet::new();
+        reached.insert(origin.clone());
+        let mut frontier = vec![origin];
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for id in frontier {
+                for neighbor in neighbors.get(id.as_str()).into_iter().flatten() {
+                    if reached.insert(neighbor.to_string()) {
+                        next.push(neighbor.to_string());
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        for id in &reached {
+            let Some(issue) = self.issues.get(id) else {
+                continue;
+            };
+            let node_type = if issue.issue_type.as_deref() == Some("Epic") {
+                "epic"
+            } else {
+                "issue"
+            };
+            graph.nodes.push(DagNode {
+                id: issue.id.clone(),
+                title: issue.title.clone(),
+                status: issue.status.clone(),
+                node_type: node_type.to_string(),
+                blocked_by: self.blocked_by(issue),
+                layer: 0,
+                in_degree: 0,
+                out_degree: 0,
+            });
+        }
+        graph.nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for (from, to) in directed {
+            if reached.contains(&from) && reached.contains(&to) {
+                graph.edges.push(DagEdge {
+                    from,
+                    to,
+                    edge_type: EdgeType::Blocks,
+                });
+            }
+        }
+
+        graph.assign_layers();
+        graph.assign_degrees();
+        let summary = graph.compute_summary();
+        graph.summary = Some(summary);
+        graph
+    }
+
+    /// Unresolved blocking dependencies for `issue`. Status comes from the
+    /// issue map when the dependency is cached; otherwise we fall back to the
+    /// inline status that `bd show` embeds in the dependency object, and
+    /// finally to treating the dependency as still blocking.
+    fn blocked_by(&self, issue: &Issue) -> Vec<String> {
+        issue
+            .dependencies_detailed()
+            .into_iter()
+            .filter(|dep| {
+                let status = resolve_issue_id(&dep.id, self.issues)
+                    .and_then(|id| self.issues.get(&id))
+                    .map(|dep_issue| dep_issue.status.clone())
+                    .or_else(|| dep.status.clone());
+                match status {
+                    Some(status) => !status_is_closed(&status),
+                    None => true,
+                }
+            })
+            .map(|dep| dep.id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub(crate) fn issue(value: serde_json::Value) -> Issue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn issue_map(issues: Vec<Issue>) -> HashMap<String, Issue> {
+        issues.into_iter().map(|i| (i.id.clone(), i)).collect()
+    }
+
+    /// The classic diamond: 1 → {2, 3} → 4, everything open, plus a pending
+    /// gate on the merge node.
+    pub(crate) fn diamond_fixture() -> (HashMap<String, Issue>, Vec<Gate>) {
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "base", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "left", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "right", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.4", "title": "merge", "status": "open",
+                "dependencies": ["bd-e.2", "bd-e.3"]
+            })),
+        ]);
+        let gates = vec![serde_json::from_value(json!({
+            "id": "gate-1", "issue_id": "bd-e.4", "status": "pending"
+        }))
+        .unwrap()];
+        (issues, gates)
+    }
+
+    #[test]
+    fn blocked_by_uses_inline_status_for_uncached_dependency() {
+        // bd-e.2 depends on bd-e.1 (closed, known only via the inline status)
+        // and on bd-x (open per the inline status, not in the map either).
+        let issues = issue_map(vec![issue(json!({
+            "id": "bd-e.2",
+            "title": "child",
+            "status": "open",
+            "dependencies": [
+                {"id": "bd-e.1", "status": "closed"},
+                {"id": "bd-x", "status": "open"}
+            ]
+        }))]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let node = graph.nodes.iter().find(|n| n.id == "bd-e.2").unwrap();
+        assert_eq!(node.blocked_by, vec!["bd-x"]);
+    }
+
+    #[test]
+    fn short_form_dependency_links_to_full_form_node() {
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "my-project-abc.1",
+                "title": "dep",
+                "status": "closed",
+                "parent": "my-project-abc"
+            })),
+            issue(json!({
+                "id": "my-project-abc.2",
+                "title": "child",
+                "status": "open",
+                "parent": "my-project-abc",
+                "dependencies": ["abc.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("my-project-abc", None);
+
+        let edge = graph
+            .edges
+            .iter()
+            .find(|e| e.to == "my-project-abc.2")
+            .unwrap();
+        assert_eq!(edge.from, "my-project-abc.1");
+
+        // The resolved dependency is closed, so the child isn't blocked.
+        let node = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == "my-project-abc.2")
+            .unwrap();
+        assert!(node.blocked_by.is_empty());
+    }
+
+    #[test]
+    fn summary_matches_diamond_fixture() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let summary = graph.summary.clone().unwrap();
+        assert_eq!(summary.node_count, 5);
+        assert_eq!(summary.edge_count, 5);
+        assert_eq!(summary.max_depth, 3);
+        assert_eq!(summary.blocked_nodes, 3);
+        assert_eq!(summary.pending_gates, 1);
+        assert!(!summary.has_cycle);
+    }
+
+    #[test]
+    fn two_and_three_node_cycles_are_detected() {
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "a", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let cycles = graph.detect_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 2);
+        assert!(graph.summary.unwrap().has_cycle);
+
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "a", "status": "open",
+                "dependencies": ["bd-e.3"]
+            })),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "c", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+        ]);
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let cycles = graph.detect_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+    }
+
+    #[test]
+    fn layers_follow_longest_path_from_roots() {
+        // Linear chain: 1 → 2 → 3.
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "c", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let layer = |id: &str| graph.nodes.iter().find(|n| n.id == id).unwrap().layer;
+        assert_eq!(layer("bd-e.1"), 0);
+        assert_eq!(layer("bd-e.2"), 1);
+        assert_eq!(layer("bd-e.3"), 2);
+
+        // Diamond: both branches sit on layer 1, the merge on layer 2.
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let layer = |id: &str| graph.nodes.iter().find(|n| n.id == id).unwrap().layer;
+        assert_eq!(layer("bd-e.1"), 0);
+        assert_eq!(layer("bd-e.2"), 1);
+        assert_eq!(layer("bd-e.3"), 1);
+        assert_eq!(layer("bd-e.4"), 2);
+    }
+
+    #[test]
+    fn degrees_match_the_diamond_shape() {
+        // Gate-free diamond so only the dependency edges set the degrees.
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "base", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "left", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "right", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.4", "title": "merge", "status": "open",
+                "dependencies": ["bd-e.2", "bd-e.3"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let node = |id: &str| graph.nodes.iter().find(|n| n.id == id).unwrap();
+
+        assert_eq!(node("bd-e.1").in_degree, 0);
+        assert_eq!(node("bd-e.1").out_degree, 2);
+        assert_eq!(node("bd-e.2").in_degree, 1);
+        assert_eq!(node("bd-e.2").out_degree, 1);
+        assert_eq!(node("bd-e.4").in_degree, 2);
+        assert_eq!(node("bd-e.4").out_degree, 0);
+    }
+
+    #[test]
+    fn cycle_members_get_the_sentinel_layer() {
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "a", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert!(graph.nodes.iter().all(|n| n.layer == usize::MAX));
+    }
+
+    #[test]
+    fn diamond_has_no_cycles() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert!(graph.detect_cycles().is_empty());
+    }
+
+    #[test]
+    fn gate_on_out_of_epic_issue_is_excluded() {
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "member", "status": "open"})),
+            issue(json!({"id": "bd-other.1", "title": "outsider", "status": "open"})),
+        ]);
+        let gates: Vec<Gate> = vec![
+            serde_json::from_value(json!({
+                "id": "gate-1", "issue_id": "bd-e.1", "status": "pending"
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({
+                "id": "gate-2", "issue_id": "bd-other.1", "status": "pending"
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({
+                "id": "gate-3", "issue_id": "bd-missing", "status": "pending"
+            }))
+            .unwrap(),
+        ];
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let gate_nodes: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == "gate")
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(gate_nodes, vec!["gate-1"]);
+    }
+
+    #[test]
+    fn ambiguous_short_id_is_left_unresolved() {
+        let issues = issue_map(vec![
+            issue(json!({"id": "proj-a-abc.1", "title": "x", "status": "open"})),
+            issue(json!({"id": "proj-b-abc.1", "title": "y", "status": "open"})),
+        ]);
+        assert_eq!(resolve_issue_id("abc.1", &issues), None);
+    }
+
+    #[test]
+    fn blocked_by_prefers_cached_status_over_inline() {
+        // The cache knows bd-e.1 is closed even though the stale inline
+        // status still says open.
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1",
+                "title": "dep",
+                "status": "closed"
+            })),
+            issue(json!({
+                "id": "bd-e.2",
+                "title": "child",
+                "status": "open",
+                "dependencies": [{"id": "bd-e.1", "status": "open"}]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let node = graph.nodes.iter().find(|n| n.id == "bd-e.2").unwrap();
+        assert!(node.blocked_by.is_empty());
+    }
+
+    #[test]
+    fn dot_export_has_one_line_per_node_and_edge() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let dot = graph.to_dot();
+
+        assert!(dot.starts_with("digraph epic {"));
+        assert!(dot.ends_with("}\n"));
+        let node_lines = dot.lines().filter(|l| l.contains("[label=")).count();
+        let edge_lines = dot.lines().filter(|l| l.contains(" -> ")).count();
+        assert_eq!(node_lines, 5); // 4 issues + 1 gate
+        assert_eq!(edge_lines, 5); // 4 dependency edges + 1 gate edge
+        assert!(dot.contains("fillcolor=lightpink")); // the gate node
+        assert!(dot.contains("[style=solid]"));
+    }
+
+    #[test]
+    fn dot_export_escapes_titles() {
+        let issues = issue_map(vec![issue(json!({
+            "id": "bd-e.1",
+            "title": "say \"hi\"\nthen stop",
+            "status": "open"
+        }))]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let dot = graph.to_dot();
+        assert!(dot.contains(r#"label="say \"hi\"\nthen stop""#));
+    }
+
+    #[test]
+    fn mermaid_ids_are_remapped_consistently() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        let mermaid = graph.to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        // Same remapped ID appears both in the node declaration and on the
+        // edges that touch it; no raw dotted/dashed IDs leak through.
+        assert!(mermaid.contains("n_bd_e_1[\"base\"]"));
+        assert!(mermaid.contains("n_bd_e_1 --> n_bd_e_2"));
+        assert!(mermaid.contains("n_gate_1 --> n_bd_e_4"));
+        assert!(!mermaid.contains("bd-e.1"));
+        assert!(mermaid.contains("class n_bd_e_1 open"));
+    }
+
+    #[test]
+    fn dependent_only_link_yields_exactly_one_edge() {
+        // bd-e.1 knows about its dependent; bd-e.2's own dependencies field
+        // was never populated (it came from `bd list`).
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "dep", "status": "open",
+                "dependents": ["bd-e.2"]
+            })),
+            issue(json!({"id": "bd-e.2", "title": "child", "status": "open"})),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+
+        let matching: Vec<_> = graph
+            .edges
+            .iter()
+            .filter(|e| e.from == "bd-e.1" && e.to == "bd-e.2")
+            .collect();
+        assert_eq!(matching.len(), 1);
+    }
+
+    #[test]
+    fn related_issue_yields_a_single_soft_edge() {
+        // bd-e.1 relates to bd-e.2 (no dependency) and to bd-x, which isn't
+        // in the graph; only the in-graph pair gets an edge. The mutual
+        // declaration on bd-e.2 must not produce a second one.
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "a", "status": "open",
+                "related": ["bd-e.2", "bd-x"]
+            })),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "related": ["bd-e.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].edge_type, EdgeType::RelatesTo);
+    }
+
+    #[test]
+    fn dependency_edge_wins_over_a_redundant_related_entry() {
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "dep", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "child", "status": "open",
+                "dependencies": ["bd-e.1"],
+                "related": ["bd-e.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].edge_type, EdgeType::Blocks);
+    }
+
+    #[test]
+    fn dependent_edge_is_not_duplicated_when_both_sides_present() {
+        let issues = issue_map(vec![
+            issue(json!({
+                "id": "bd-e.1", "title": "dep", "status": "open",
+                "dependents": ["bd-e.2"]
+            })),
+            issue(json!({
+                "id": "bd-e.2", "title": "child", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert_eq!(graph.edges.len(), 1);
+    }
+
+    fn chain_fixture() -> HashMap<String, Issue> {
+        issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "c", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+            issue(json!({
+                "id": "bd-e.4", "title": "d", "status": "open",
+                "dependencies": ["bd-e.3"]
+            })),
+            issue(json!({
+                "id": "bd-e.5", "title": "e", "status": "open",
+                "dependencies": ["bd-e.4"]
+            })),
+        ])
+    }
+
+    #[test]
+    fn neighborhood_depth_one_returns_direct_neighbors_only() {
+        let issues = chain_fixture();
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_neighborhood("bd-e.3", 1);
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["bd-e.2", "bd-e.3", "bd-e.4"]);
+        assert_eq!(graph.edges.len(), 2);
+    }
+
+    #[test]
+    fn neighborhood_depth_two_reaches_both_chain_ends() {
+        let issues = chain_fixture();
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_neighborhood("bd-e.3", 2);
+        assert_eq!(graph.nodes.len(), 5);
+        assert_eq!(graph.edges.len(), 4);
+    }
+
+    #[test]
+    fn neighborhood_of_unknown_issue_is_empty() {
+        let issues = chain_fixture();
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_neighborhood("bd-nope", 3);
+        assert!(graph.nodes.is_empty());
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn status_filter_bridges_dropped_intermediate_nodes() {
+        // A (open) → B (closed) → C (open); filtering to open drops B but
+        // must leave A → C so the chain stays connected.
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "closed",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "c", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let filter = vec!["open".to_string()];
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", Some(&filter));
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"bd-e.1") && ids.contains(&"bd-e.3"));
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].from, "bd-e.1");
+        assert_eq!(graph.edges[0].to, "bd-e.3");
+    }
+
+    #[test]
+    fn status_filter_keeps_direct_edges_between_kept_nodes() {
+        let (issues, gates) = diamond_fixture();
+        let filter = vec!["open".to_string()];
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", Some(&filter));
+        // Everything is open except the pending gate, which drops out; its
+        // edge has no kept source to bridge from.
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 4);
+    }
+
+    #[test]
+    fn progress_counts_closed_issue_nodes_only() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert_eq!(graph.progress(), 0.0);
+
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "done"})),
+            issue(json!({"id": "bd-e.2", "title": "b", "status": "closed"})),
+        ]);
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert_eq!(graph.progress(), 1.0);
+
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "completed"})),
+            issue(json!({"id": "bd-e.2", "title": "b", "status": "open"})),
+            issue(json!({"id": "bd-e.3", "title": "c", "status": "open"})),
+            issue(json!({"id": "bd-e.4", "title": "d", "status": "open"})),
+        ]);
+        // The pending gate on bd-e.4 stays out of the denominator.
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert_eq!(graph.progress(), 0.25);
+    }
+
+    #[test]
+    fn critical_path_covers_the_whole_linear_chain() {
+        let issues = issue_map(vec![
+            issue(json!({"id": "bd-e.1", "title": "a", "status": "open"})),
+            issue(json!({
+                "id": "bd-e.2", "title": "b", "status": "open",
+                "dependencies": ["bd-e.1"]
+            })),
+            issue(json!({
+                "id": "bd-e.3", "title": "c", "status": "open",
+                "dependencies": ["bd-e.2"]
+            })),
+        ]);
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        assert_eq!(graph.critical_path(), vec!["bd-e.1", "bd-e.2", "bd-e.3"]);
+    }
+
+    #[test]
+    fn critical_path_picks_one_diamond_branch_deterministically() {
+        let (issues, gates) = diamond_fixture();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-e", None);
+        // Both branches tie at length 3; the lexicographically smaller one
+        // (through bd-e.2) wins.
+        assert_eq!(graph.critical_path(), vec!["bd-e.1", "bd-e.2", "bd-e.4"]);
+    }
+
+    #[test]
+    fn empty_graph_renders_a_minimal_valid_diagram() {
+        let issues = HashMap::new();
+        let gates = Vec::new();
+        let graph = DagBuilder::new(&issues, &gates).build_dag("bd-none", None);
+        assert_eq!(graph.to_mermaid(), "flowchart TD\n  empty[\"no nodes\"]\n");
+    }
+}
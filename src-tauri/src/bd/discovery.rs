@@ -0,0 +1,290 @@
+//! Discovery of bd workspaces via the global beads registry.
+//!
+//! bd records every workspace it has initialized in
+//! `~/.beads/registry.json`; the dashboard reads that file to offer a
+//! workspace switcher without scanning the filesystem.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use super::client::{BdError, BdResult};
+
+/// How long to sit out after the first change notification before
+/// re-reading the registry. bd rewrites the whole file on every daemon
+/// heartbeat, so raw notify events arrive in bursts.
+const REGISTRY_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// One workspace known to bd.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub workspace_path: PathBuf,
+    /// Display name, when bd recorded one.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Fields this version doesn't model (prefix, last-seen timestamps, …).
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// A registry entry enriched with what we can see on disk right now.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceInfo {
+    pub workspace_path: PathBuf,
+    pub name: Option<String>,
+    /// Whether the workspace directory still exists.
+    pub exists: bool,
+    /// Whether the workspace's daemon socket is present.
+    pub daemon_running: bool,
+}
+
+/// Reader for the beads workspace registry.
+pub struct WorkspaceDiscovery {
+    registry_path: PathBuf,
+}
+
+impl WorkspaceDiscovery {
+    /// Discovery against the default registry location; `None` when the
+    /// platform has no home dir to anchor it.
+    pub fn new() -> Option<Self> {
+        dirs::home_dir().map(|home| Self {
+            registry_path: home.join(".beads").join("registry.json"),
+        })
+    }
+
+    pub fn with_registry_path(registry_path: impl Into<PathBuf>) -> Self {
+        Self {
+            registry_path: registry_path.into(),
+        }
+    }
+
+    pub fn registry_path(&self) -> &Path {
+        &self.registry_path
+    }
+
+    /// Read the registry. A missing file means bd has never initialized a
+    /// workspace here — that's an empty list, not an error.
+    ///
+    /// Older bd versions write a JSON array of entries; newer ones write an
+    /// object keyed by workspace path. Both shapes are accepted and come
+    /// back sorted by path so callers see one stable order.
+    pub fn load_registry(&self) -> BdResult<Vec<RegistryEntry>> {
+        let bytes = match std::fs::read(&self.registry_path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(BdError::Io(err)),
+        };
+        let mut entries = parse_registry(&bytes)?;
+        entries.sort_by(|a, b| a.workspace_path.cmp(&b.workspace_path));
+        Ok(entries)
+    }
+
+    /// [`load_registry`](Self::load_registry) plus an on-disk health look:
+    /// does the directory still exist, and is its daemon socket present.
+    pub fn discover(&self) -> BdResult<Vec<WorkspaceInfo>> {
+        Ok(self
+            .load_registry()?
+            .into_iter()
+            .map(|entry| {
+                let exists = entry.workspace_path.is_dir();
+                let daemon_running = entry
+                    .workspace_path
+                    .join(".beads")
+                    .join("bd.sock")
+                    .exists();
+                WorkspaceInfo {
+                    workspace_path: entry.workspace_path,
+                    name: entry.name,
+                    exists,
+                    daemon_running,
+                }
+            })
+            .collect())
+    }
+
+    /// Watch the registry and deliver a refreshed [`discover`](Self::discover)
+    /// result after each (debounced) burst of writes. The notify watcher
+    /// lives inside the forwarding task, so dropping the receiver stops
+    /// the watch.
+    pub fn watch(self) -> notify::Result<mpsc::Receiver<Vec<WorkspaceInfo>>> {
+        use notify::{RecursiveMode, Watcher};
+
+        // Watch the parent directory: bd replaces the registry atomically
+        // via rename, which a file-level watch loses track of.
+        let dir = self
+            .registry_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let file_name = self.registry_path.file_name().map(|n| n.to_os_string());
+        let (ping_tx, mut ping_rx) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else { return };
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == file_name.as_deref())
+                {
+                    let _ = ping_tx.send(());
+                }
+            })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            // Moving the watcher in ties its lifetime to the loop.
+            let _watcher = watcher;
+            while ping_rx.recv().await.is_some() {
+                tokio::time::sleep(REGISTRY_DEBOUNCE).await;
+                while ping_rx.try_recv().is_ok() {}
+                match self.discover() {
+                    Ok(workspaces) => {
+                        if tx.send(workspaces).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        tracing::warn!("re-discovery after registry change failed: {err}");
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Parse either registry shape: an array of entries, or an object mapping
+/// workspace path to the rest of the entry.
+fn parse_registry(bytes: &[u8]) -> BdResult<Vec<RegistryEntry>> {
+    if let Ok(entries) = serde_json::from_slice::<Vec<RegistryEntry>>(bytes) {
+        return Ok(entries);
+    }
+    let by_path: HashMap<String, Value> = serde_json::from_slice(bytes)?;
+    by_path
+        .into_iter()
+        .map(|(path, value)| {
+            let mut entry = match value {
+                Value::Object(map) => map,
+                // bd writes `{"path": true}` placeholders in some versions.
+                _ => serde_json::Map::new(),
+            };
+            entry.insert("workspace_path".to_string(), Value::String(path));
+            serde_json::from_value(Value::Object(entry)).map_err(BdError::ParseError)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn load(registry: &Value) -> Vec<RegistryEntry> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registry.json");
+        std::fs::write(&path, serde_json::to_vec(registry).unwrap()).unwrap();
+        WorkspaceDiscovery::with_registry_path(&path)
+            .load_registry()
+            .unwrap()
+    }
+
+    #[test]
+    fn array_and_object_registries_parse_the_same() {
+        let array = load(&json!([
+            {"workspace_path": "/work/a", "name": "alpha"},
+            {"workspace_path": "/work/b"}
+        ]));
+        let object = load(&json!({
+            "/work/b": {},
+            "/work/a": {"name": "alpha"}
+        }));
+        assert_eq!(array, object);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[0].workspace_path, PathBuf::from("/work/a"));
+        assert_eq!(array[0].name.as_deref(), Some("alpha"));
+        assert_eq!(array[1].name, None);
+    }
+
+    #[test]
+    fn unknown_fields_survive_in_extra() {
+        let entries = load(&json!({
+            "/work/a": {"prefix": "bd", "last_seen": "2026-01-01T00:00:00Z"}
+        }));
+        assert_eq!(entries[0].extra["prefix"], json!("bd"));
+    }
+
+    #[test]
+    fn missing_registry_is_an_empty_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let discovery = WorkspaceDiscovery::with_registry_path(dir.path().join("registry.json"));
+        assert!(discovery.load_registry().unwrap().is_empty());
+    }
+
+    #[test]
+    fn discover_reports_disk_and_daemon_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let live = dir.path().join("live");
+        std::fs::create_dir_all(live.join(".beads")).unwrap();
+        std::fs::write(live.join(".beads").join("bd.sock"), b"").unwrap();
+
+        let path = dir.path().join("registry.json");
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&json!({
+                live.to_str().unwrap(): {},
+                "/definitely/missing": {}
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let workspaces = WorkspaceDiscovery::with_registry_path(&path)
+            .discover()
+            .unwrap();
+        let missing = workspaces
+            .iter()
+            .find(|w| w.workspace_path == Path::new("/definitely/missing"))
+            .unwrap();
+        assert!(!missing.exists);
+        assert!(!missing.daemon_running);
+        let live_ws = workspaces.iter().find(|w| w.workspace_path == live).unwrap();
+        assert!(live_ws.exists);
+        assert!(live_ws.daemon_running);
+    }
+
+    #[tokio::test]
+    async fn registry_writes_produce_a_debounced_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registry.json");
+        std::fs::write(&path, b"[]").unwrap();
+
+        let mut rx = WorkspaceDiscovery::with_registry_path(&path).watch().unwrap();
+        std::fs::write(
+            &path,
+            serde_json::to_vec(&json!({"/work/a": {}})).unwrap(),
+        )
+        .unwrap();
+
+        let update = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .expect("no update within 10s")
+            .expect("watch channel closed");
+        assert_eq!(update.len(), 1);
+        assert_eq!(update[0].workspace_path, Path::new("/work/a"));
+    }
+
+    #[test]
+    fn garbage_registry_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registry.json");
+        std::fs::write(&path, b"not json").unwrap();
+        assert!(WorkspaceDiscovery::with_registry_path(&path)
+            .load_registry()
+            .is_err());
+    }
+}
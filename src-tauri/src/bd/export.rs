@@ -0,0 +1,255 @@
+//! Plain-text/report exports built from cached data.
+
+use super::cache::BeadsCache;
+use super::dag::is_issue_in_epic;
+use super::status::status_is_closed;
+use super::types::Issue;
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Render one epic's issues as a Markdown status report: header with a
+/// progress bar, an issue table, and a dependency outline. Returns `None`
+/// when no issue or epic under `epic_id` exists in the cache.
+pub fn epic_markdown(cache: &BeadsCache, epic_id: &str) -> Option<String> {
+    let mut issues: Vec<Issue> = cache
+        .issues_map()
+        .values()
+        .filter(|issue| is_issue_in_epic(issue, epic_id))
+        .cloned()
+        .collect();
+    let epic = cache.get_epic_status(epic_id);
+    if issues.is_empty() && epic.is_none() {
+        return None;
+    }
+    issues.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let title = epic
+        .map(|e| e.title.clone())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| epic_id.to_string());
+    let closed = issues.iter().filter(|i| status_is_closed(&i.status)).count();
+
+    let mut out = String::new();
+    out.push_str(&format!("# Epic: {title} ({epic_id})\n\n"));
+    out.push_str(&format!(
+        "Progress: {closed}/{} closed {}\n\n",
+        issues.len(),
+        progress_bar(closed, issues.len())
+    ));
+
+    out.push_str("| ID | Title | Status | Assignee |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for issue in &issues {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            issue.id,
+            escape_cell(&issue.title),
+            issue.status,
+            issue.effective_assignee().unwrap_or("—"),
+        ));
+    }
+
+    let with_deps: Vec<&Issue> = issues
+        .iter()
+        .filter(|i| !i.dependency_ids().is_empty())
+        .collect();
+    if !with_deps.is_empty() {
+        out.push_str("\n## Dependencies\n\n");
+        for issue in with_deps {
+            out.push_str(&format!(
+                "- {} depends on {}\n",
+                issue.id,
+                issue.dependency_ids().join(", ")
+            ));
+        }
+    }
+
+    Some(out)
+}
+
+/// Stream `issues` to `path` as JSON Lines — one object per line, written
+/// through a buffered async writer rather than assembled into one giant
+/// string. Returns the number of lines written. Issues are sorted by ID so
+/// successive dumps of the same workspace diff cleanly.
+pub async fn issues_jsonl(mut issues: Vec<Issue>, path: &std::path::Path) -> std::io::Result<usize> {
+    use tokio::io::AsyncWriteExt;
+
+    issues.sort_by(|a, b| a.id.cmp(&b.id));
+    let file = tokio::fs::File::create(path).await?;
+    let mut writer = tokio::io::BufWriter::new(file);
+    let mut written = 0;
+    for issue in &issues {
+        let line = serde_json::to_vec(issue)?;
+        writer.write_all(&line).await?;
+        writer.write_all(b"\n").await?;
+        written += 1;
+    }
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// Write `issues` to `path` as CSV with a header row, for the stakeholders
+/// who live in spreadsheets. Labels are joined with `;` into one column and
+/// the priority column carries bd's numeric rank (empty when unset), via the
+/// same extraction the ready-work sort uses. Returns the number of data rows.
+pub async fn issues_csv(mut issues: Vec<Issue>, path: &std::path::Path) -> std::io::Result<usize> {
+    use tokio::io::AsyncWriteExt;
+
+    issues.sort_by(|a, b| a.id.cmp(&b.id));
+    let file = tokio::fs::File::create(path).await?;
+    let mut writer = tokio::io::BufWriter::new(file);
+    writer.write_all(b"id,title,status,priority,assignee,labels\n").await?;
+    let mut written = 0;
+    for issue in &issues {
+        let priority = match super::recommend::priority_rank(issue) {
+            i64::MAX => String::new(),
+            rank => rank.to_string(),
+        };
+        let row = [
+            csv_field(&issue.id),
+            csv_field(&issue.title),
+            csv_field(&issue.status),
+            priority,
+            csv_field(issue.effective_assignee().unwrap_or("")),
+            csv_field(&issue.labels.join(";")),
+        ]
+        .join(",");
+        writer.write_all(row.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        written += 1;
+    }
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// Quote a CSV field only when it needs it (commas, quotes, or newlines),
+/// doubling embedded quotes per RFC 4180.
+fn csv_field(raw: &str) -> String {
+    if raw.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", raw.replace('"', "\"\""))
+    } else {
+        raw.to_string()
+    }
+}
+
+fn progress_bar(done: usize, total: usize) -> String {
+    if total == 0 {
+        return String::new();
+    }
+    let filled = (done * PROGRESS_BAR_WIDTH) / total;
+    format!(
+        "`[{}{}]` {}%",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled),
+        (done * 100) / total
+    )
+}
+
+/// Keep titles from breaking the Markdown table.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache_with_epic() -> BeadsCache {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                serde_json::from_value(json!({
+                    "id": "bd-e.1", "title": "First | task", "status": "closed",
+                    "assignee": "alice"
+                }))
+                .unwrap(),
+                serde_json::from_value(json!({
+                    "id": "bd-e.2", "title": "Second task", "status": "open",
+                    "dependencies": ["bd-e.1"]
+                }))
+                .unwrap(),
+            ],
+            vec![],
+            vec![serde_json::from_value(json!({"epic_id": "bd-e", "title": "The Epic"})).unwrap()],
+        );
+        cache
+    }
+
+    #[test]
+    fn markdown_contains_header_and_a_row_per_issue() {
+        let cache = cache_with_epic();
+        let md = epic_markdown(&cache, "bd-e").unwrap();
+        assert!(md.starts_with("# Epic: The Epic (bd-e)"));
+        assert!(md.contains("Progress: 1/2 closed"));
+        assert!(md.contains("| bd-e.1 | First \\| task | closed | alice |"));
+        assert!(md.contains("| bd-e.2 | Second task | open | — |"));
+        assert!(md.contains("- bd-e.2 depends on bd-e.1"));
+    }
+
+    #[test]
+    fn unknown_epic_returns_none() {
+        let cache = cache_with_epic();
+        assert!(epic_markdown(&cache, "bd-nope").is_none());
+    }
+
+    #[tokio::test]
+    async fn jsonl_writes_one_parseable_line_per_issue() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("issues.jsonl");
+        let issues = cache_with_epic().list_issues();
+
+        let written = issues_jsonl(issues, &path).await.unwrap();
+        assert_eq!(written, 2);
+
+        let dump = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: Issue = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first.id, "bd-e.1", "sorted by id");
+    }
+
+    #[tokio::test]
+    async fn csv_quotes_fields_that_need_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("issues.csv");
+        let issues = vec![serde_json::from_value(json!({
+            "id": "bd-1", "title": "Fix parsing, quoting and \"escapes\"",
+            "status": "open", "priority": 2, "assignee": "alice",
+            "labels": ["bug", "parser"]
+        }))
+        .unwrap()];
+
+        let written = issues_csv(issues, &path).await.unwrap();
+        assert_eq!(written, 1);
+
+        let dump = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines[0], "id,title,status,priority,assignee,labels");
+        assert_eq!(
+            lines[1],
+            "bd-1,\"Fix parsing, quoting and \"\"escapes\"\"\",open,2,alice,bug;parser"
+        );
+    }
+
+    #[tokio::test]
+    async fn csv_leaves_priority_empty_when_unset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("issues.csv");
+        let issues = vec![serde_json::from_value(json!({
+            "id": "bd-1", "title": "Plain", "status": "open"
+        }))
+        .unwrap()];
+
+        issues_csv(issues, &path).await.unwrap();
+        let dump = std::fs::read_to_string(&path).unwrap();
+        assert!(dump.lines().any(|l| l == "bd-1,Plain,open,,,"));
+    }
+
+    #[tokio::test]
+    async fn jsonl_surfaces_an_unwritable_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing-dir").join("issues.jsonl");
+        assert!(issues_jsonl(Vec::new(), &path).await.is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! Workspace health checks backing the dashboard's status pane.
+//!
+//! Each check is small and independent; [`HealthChecker::run`] bundles them
+//! into one [`HealthReport`] so the frontend renders a single pass/fail list
+//! instead of probing bd itself.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::BeadsCache;
+use super::client::BdClient;
+use super::daemon::DaemonManager;
+
+/// Default maximum cache age before the health report flags it, in seconds.
+pub const MAX_CACHE_AGE_SECS: u64 = 300;
+
+/// Default number of past outcomes kept for the uptime sparkline.
+pub const HISTORY_CAPACITY: usize = 20;
+
+/// Flag the cache filesystem when less than this much space remains: a
+/// full disk makes `save_to_disk` fail silently on the background task.
+pub const MIN_DISK_BYTES: u64 = 50 * 1024 * 1024;
+
+/// One named probe result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// All checks from one run; `healthy` is the conjunction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// Condensed outcome of one run, kept in the history ring for the uptime
+/// sparkline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthStatus {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub healthy: bool,
+    /// Names of the checks that failed; empty when healthy.
+    pub failed: Vec<String>,
+}
+
+/// The two dimensions the setup UI needs separated: a missing binary means
+/// "install bd", an unresponsive daemon with the binary present means
+/// "start the daemon". `version` rides along when bd answered at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BdHealth {
+    pub bd_installed: bool,
+    pub daemon_running: bool,
+    pub version: Option<String>,
+    /// Daemon process ID, when the daemon is up and reports one.
+    pub daemon_pid: Option<u32>,
+    /// How long the daemon has been up, when it reports that.
+    pub daemon_uptime_secs: Option<f64>,
+}
+
+/// Probe bd's install/daemon state. The daemon and version probes run
+/// concurrently, like [`HealthChecker::run`]; a missing binary short-circuits
+/// both since neither can succeed without it.
+pub async fn probe_bd(client: &BdClient) -> BdHealth {
+    if !client.bd_path().is_file() {
+        return BdHealth {
+            bd_installed: false,
+            daemon_running: false,
+            version: None,
+            daemon_pid: None,
+            daemon_uptime_secs: None,
+        };
+    }
+    let manager = DaemonManager::with_binary(client.bd_path(), client.workspace());
+    let (status, version) = tokio::join!(manager.status(), client.bd_version());
+    let (daemon_pid, daemon_uptime_secs) = status
+        .as_ref()
+        .map(daemon_details)
+        .unwrap_or((None, None));
+    BdHealth {
+        bd_installed: true,
+        daemon_running: status.is_ok(),
+        version: version.map(|v| v.to_string()),
+        daemon_pid,
+        daemon_uptime_secs,
+    }
+}
+
+/// PID and uptime from the daemon-status payload. Older bd spells uptime
+/// `uptime_seconds`; both fields are optional in any version.
+fn daemon_details(status: &serde_json::Value) -> (Option<u32>, Option<f64>) {
+    let pid = status
+        .get("pid")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|p| u32::try_from(p).ok());
+    let uptime = status
+        .get("uptime_secs")
+        .or_else(|| status.get("uptime_seconds"))
+        .and_then(serde_json::Value::as_f64);
+    (pid, uptime)
+}
+
+/// Runs the workspace health checks with a configurable cache-age bound,
+/// remembering the last few outcomes so the UI can show whether bd has
+/// been flapping.
+#[derive(Debug, Clone)]
+pub struct HealthChecker {
+    max_cache_age: Duration,
+    history: VecDeque<HealthStatus>,
+    history_capacity: usize,
+}
+
+impl Default for HealthChecker {
+    fn default() -> Self {
+        Self {
+            max_cache_age: Duration::from_secs(MAX_CACHE_AGE_SECS),
+            history: VecDeque::new(),
+            history_capacity: HISTORY_CAPACITY,
+        }
+    }
+}
+
+impl HealthChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A checker with a non-default cache-age bound.
+    pub fn with_max_cache_age(max_cache_age: Duration) -> Self {
+        Self {
+            max_cache_age,
+            ..Self::default()
+        }
+    }
+
+    /// A checker retaining a non-default number of past outcomes.
+    pub fn with_history_capacity(history_capacity: usize) -> Self {
+        Self {
+            history_capacity: history_capacity.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Past outcomes, oldest first.
+    pub fn history(&self) -> Vec<HealthStatus> {
+        self.history.iter().cloned().collect()
+    }
+
+    pub fn max_cache_age(&self) -> Duration {
+        self.max_cache_age
+    }
+
+    /// Adjust the cache-age bound at runtime.
+    pub fn set_max_cache_age(&mut self, max_cache_age: Duration) {
+        self.max_cache_age = max_cache_age;
+    }
+
+    /// Run every check and bundle the results. The on-disk checks are free;
+    /// the two bd probes each cost a round-trip, so they run concurrently
+    /// rather than back to back.
+    pub async fn run(&mut self, client: &BdClient, cache: &BeadsCache) -> HealthReport {
+        let started = std::time::Instant::now();
+        let (daemon, version) =
+            tokio::join!(Self::check_daemon(client), Self::check_version(client));
+        let checks = vec![
+            Self::check_bd_binary(client),
+            Self::check_workspace(client),
+            daemon,
+            version,
+            self.check_cache_age(cache),
+            Self::check_disk_space(),
+        ];
+        let healthy = checks.iter().all(|c| c.ok);
+        tracing::debug!(
+            "health checks finished in {}ms (healthy: {healthy})",
+            started.elapsed().as_millis()
+        );
+        let report = HealthReport { healthy, checks };
+        self.record(&report);
+        report
+    }
+
+    /// Push one run's outcome onto the history ring, evicting the oldest
+    /// entries once past capacity.
+    fn record(&mut self, report: &HealthReport) {
+        self.history.push_back(HealthStatus {
+            at: chrono::Utc::now(),
+            healthy: report.healthy,
+            failed: report
+                .checks
+                .iter()
+                .filter(|c| !c.ok)
+                .map(|c| c.name.clone())
+                .collect(),
+        });
+        while self.history.len() > self.history_capacity {
+            self.history.pop_front();
+        }
+    }
+
+    /// The bd daemon answers a status probe. On unix a missing socket file
+    /// settles it without spawning bd at all — the daemon can't be running
+    /// without one; Windows has no socket, so it always takes the full probe.
+    async fn check_daemon(client: &BdClient) -> HealthCheck {
+        if cfg!(unix) && !client.socket_exists() {
+            return HealthCheck {
+                name: "daemon".to_string(),
+                ok: false,
+                detail: format!("socket missing: {}", client.socket_path().display()),
+            };
+        }
+        let manager = DaemonManager::with_binary(client.bd_path(), client.workspace());
+        let (ok, detail) = match manager.status().await {
+            Ok(_) => (true, "daemon answering".to_string()),
+            Err(err) => (false, err.to_string()),
+        };
+        HealthCheck {
+            name: "daemon".to_string(),
+            ok,
+            detail,
+        }
+    }
+
+    /// bd reports a parseable version.
+    async fn check_version(client: &BdClient) -> HealthCheck {
+        let (ok, detail) = match client.bd_version().await {
+            Some(version) => (true, version.to_string()),
+            None => (false, "version not detected".to_string()),
+        };
+        HealthCheck {
+            name: "bd_version".to_string(),
+            ok,
+            detail,
+        }
+    }
+
+    /// The resolved bd binary still exists on disk (it can disappear out
+    /// from under a long-running app, e.g. during an upgrade).
+    fn check_bd_binary(client: &BdClient) -> HealthCheck {
+        let path = client.bd_path();
+        HealthCheck {
+            name: "bd_binary".to_string(),
+            ok: path.is_file(),
+            detail: path.display().to_string(),
+        }
+    }
+
+    /// The configured workspace directory exists.
+    fn check_workspace(client: &BdClient) -> HealthCheck {
+        let path = client.workspace();
+        HealthCheck {
+            name: "workspace".to_string(),
+            ok: path.is_dir(),
+            detail: path.display().to_string(),
+        }
+    }
+
+    /// The cache directory's filesystem has room left to persist into.
+    fn check_disk_space() -> HealthCheck {
+        let available = BeadsCache::default_cache_dir()
+            .as_deref()
+            .and_then(nearest_existing)
+            .and_then(|dir| fs2::available_space(dir).ok());
+        disk_check(available, MIN_DISK_BYTES)
+    }
+
+    /// The cache has synced recently enough to trust.
+    fn check_cache_age(&self, cache: &BeadsCache) -> HealthCheck {
+        let (ok, detail) = match cache.age_secs() {
+            Some(age) => (
+                age <= self.max_cache_age.as_secs(),
+                format!("last sync {age}s ago (max {}s)", self.max_cache_age.as_secs()),
+            ),
+            None => (false, "never synced".to_string()),
+        };
+        HealthCheck {
+            name: "cache_age".to_string(),
+            ok,
+            detail,
+        }
+    }
+}
+
+/// The cache dir usually doesn't exist until the first save; stat the
+/// nearest ancestor that does, since it lives on the same filesystem.
+fn nearest_existing(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.exists())
+}
+
+/// Pure threshold comparison, split out so tests can inject byte counts.
+/// An unresolvable cache dir passes: nothing will be persisted there
+/// anyway, and that's the cache's problem, not the disk's.
+fn disk_check(available: Option<u64>, min: u64) -> HealthCheck {
+    const MB: u64 = 1024 * 1024;
+    let (ok, detail) = match available {
+        Some(bytes) if bytes < min => (
+            false,
+            format!("{} MB free (min {} MB)", bytes / MB, min / MB),
+        ),
+        Some(bytes) => (true, format!("{} MB free", bytes / MB)),
+        None => (true, "cache dir not resolvable".to_string()),
+    };
+    HealthCheck {
+        name: "disk_space".to_string(),
+        ok,
+        detail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn cache_age_check_respects_the_configured_bound() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(vec![], vec![], vec![]);
+        cache.set_last_sync_for_tests(Instant::now() - Duration::from_secs(60));
+
+        let strict = HealthChecker::with_max_cache_age(Duration::from_secs(30));
+        assert!(!strict.check_cache_age(&cache).ok);
+
+        let relaxed = HealthChecker::with_max_cache_age(Duration::from_secs(120));
+        assert!(relaxed.check_cache_age(&cache).ok);
+    }
+
+    #[test]
+    fn unsynced_cache_fails_the_age_check() {
+        let cache = BeadsCache::new();
+        let check = HealthChecker::new().check_cache_age(&cache);
+        assert!(!check.ok);
+        assert_eq!(check.detail, "never synced");
+    }
+
+    #[test]
+    fn disk_check_flags_space_below_the_threshold() {
+        assert!(!disk_check(Some(MIN_DISK_BYTES - 1), MIN_DISK_BYTES).ok);
+        assert!(disk_check(Some(MIN_DISK_BYTES), MIN_DISK_BYTES).ok);
+        assert!(disk_check(Some(u64::MAX), MIN_DISK_BYTES).ok);
+        // No cache dir means nothing to fill up.
+        assert!(disk_check(None, MIN_DISK_BYTES).ok);
+    }
+
+    #[test]
+    fn history_evicts_the_oldest_past_capacity() {
+        let mut checker = HealthChecker::with_history_capacity(3);
+        for i in 0..5 {
+            checker.record(&HealthReport {
+                healthy: i % 2 == 0,
+                checks: vec![],
+            });
+        }
+        let history = checker.history();
+        assert_eq!(history.len(), 3);
+        // Runs 0 and 1 were evicted; 2, 3, 4 remain, oldest first.
+        assert!(history[0].healthy);
+        assert!(!history[1].healthy);
+        assert!(history[2].healthy);
+    }
+
+    #[test]
+    fn failed_check_names_land_in_the_status() {
+        let mut checker = HealthChecker::new();
+        checker.record(&HealthReport {
+            healthy: false,
+            checks: vec![
+                HealthCheck {
+                    name: "daemon".to_string(),
+                    ok: false,
+                    detail: "no answer".to_string(),
+                },
+                HealthCheck {
+                    name: "workspace".to_string(),
+                    ok: true,
+                    detail: "/tmp".to_string(),
+                },
+            ],
+        });
+        assert_eq!(checker.history()[0].failed, vec!["daemon".to_string()]);
+    }
+
+    #[cfg(unix)]
+    fn fake_bd(dir: &Path, body: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.join("bd");
+        std::fs::write(&script, format!("#!/bin/sh\n{body}")).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[tokio::test]
+    async fn missing_binary_reports_nothing_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = BdClient::with_binary(dir.path().join("no-such-bd"), dir.path());
+        let health = probe_bd(&client).await;
+        assert!(!health.bd_installed);
+        assert!(!health.daemon_running);
+        assert_eq!(health.version, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stopped_daemon_still_reports_the_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "case \"$1\" in\n\
+             --version) echo 'bd version 0.47.0';;\n\
+             daemon) exit 1;;\n\
+             *) echo '{}';;\n\
+             esac\n",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+        let health = probe_bd(&client).await;
+        assert!(health.bd_installed);
+        assert!(!health.daemon_running);
+        assert_eq!(health.version.as_deref(), Some("0.47.0"));
+        assert_eq!(health.daemon_pid, None);
+        assert_eq!(health.daemon_uptime_secs, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn running_daemon_reports_all_green() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = fake_bd(
+            dir.path(),
+            "case \"$1\" in\n\
+             --version) echo '0.47.0';;\n\
+             *) echo '{\"pid\": 4242, \"uptime_secs\": 12.5}';;\n\
+             esac\n",
+        );
+        let client = BdClient::with_binary(&script, dir.path());
+        let health = probe_bd(&client).await;
+        assert!(health.bd_installed);
+        assert!(health.daemon_running);
+        assert_eq!(health.version.as_deref(), Some("0.47.0"));
+        assert_eq!(health.daemon_pid, Some(4242));
+        assert_eq!(health.daemon_uptime_secs, Some(12.5));
+    }
+
+    #[test]
+    fn daemon_details_tolerate_either_uptime_spelling() {
+        let (pid, uptime) =
+            daemon_details(&serde_json::json!({"pid": 7, "uptime_seconds": 3.0}));
+        assert_eq!((pid, uptime), (Some(7), Some(3.0)));
+        assert_eq!(daemon_details(&serde_json::json!({})), (None, None));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn bd_probes_run_concurrently_not_in_sequence() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("bd");
+        // Every invocation sleeps; two sequential probes would take >= 1s.
+        std::fs::write(&script, "#!/bin/sh\nsleep 0.5\necho '{}'\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let client = BdClient::with_binary(&script, dir.path());
+
+        let started = Instant::now();
+        let mut checker = HealthChecker::new();
+        checker.run(&client, &BeadsCache::new()).await;
+        assert!(
+            started.elapsed() < Duration::from_millis(950),
+            "probes appear to have run sequentially: {:?}",
+            started.elapsed()
+        );
+    }
+}
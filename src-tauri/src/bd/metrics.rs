@@ -0,0 +1,147 @@
+//! Flow analytics computed from cached data.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::BeadsCache;
+use super::status::status_is_closed;
+
+/// Aggregate lead-time statistics over one population of closed issues.
+/// Durations are in seconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeadTimeStats {
+    pub count: usize,
+    pub mean_secs: f64,
+    pub median_secs: f64,
+    pub p90_secs: f64,
+}
+
+/// Lead times across the whole cache plus a per-assignee breakdown. Issues
+/// under nobody land in the `"unassigned"` bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeadTimeReport {
+    pub overall: Option<LeadTimeStats>,
+    pub per_assignee: BTreeMap<String, LeadTimeStats>,
+    /// Closed issues skipped because `created_at` or `closed_at` was missing
+    /// or unparseable.
+    pub excluded: usize,
+}
+
+/// Lead time (created → closed) for every closed issue with both timestamps.
+pub fn lead_times(cache: &BeadsCache) -> LeadTimeReport {
+    let mut overall = Vec::new();
+    let mut per_assignee: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut excluded = 0;
+
+    for issue in cache.issues_map().values() {
+        if !status_is_closed(&issue.status) {
+            continue;
+        }
+        let (Some(created), Some(closed)) = (issue.created_at_ts(), issue.closed_at_ts()) else {
+            excluded += 1;
+            continue;
+        };
+        let secs = closed.signed_duration_since(created).num_seconds();
+        if secs < 0 {
+            excluded += 1;
+            continue;
+        }
+        let secs = secs as f64;
+        overall.push(secs);
+        let assignee = issue.effective_assignee().unwrap_or("unassigned");
+        per_assignee.entry(assignee.to_string()).or_default().push(secs);
+    }
+
+    LeadTimeReport {
+        overall: stats(overall),
+        per_assignee: per_assignee
+            .into_iter()
+            .filter_map(|(assignee, secs)| stats(secs).map(|s| (assignee, s)))
+            .collect(),
+        excluded,
+    }
+}
+
+fn stats(mut secs: Vec<f64>) -> Option<LeadTimeStats> {
+    if secs.is_empty() {
+        return None;
+    }
+    secs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let count = secs.len();
+    let mean = secs.iter().sum::<f64>() / count as f64;
+    Some(LeadTimeStats {
+        count,
+        mean_secs: mean,
+        median_secs: percentile(&secs, 0.5),
+        p90_secs: percentile(&secs, 0.9),
+    })
+}
+
+/// Nearest-rank percentile over an already sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((p * sorted.len() as f64).ceil() as usize).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn closed_issue(id: &str, assignee: &str, created: &str, closed: &str) -> crate::bd::Issue {
+        serde_json::from_value(json!({
+            "id": id, "title": "t", "status": "closed", "assignee": assignee,
+            "created_at": created, "closed_at": closed
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn medians_match_known_fixture() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                // alice: 1h, 3h, 5h → median 3h
+                closed_issue("bd-1", "alice", "2024-01-01T00:00:00Z", "2024-01-01T01:00:00Z"),
+                closed_issue("bd-2", "alice", "2024-01-01T00:00:00Z", "2024-01-01T03:00:00Z"),
+                closed_issue("bd-3", "alice", "2024-01-01T00:00:00Z", "2024-01-01T05:00:00Z"),
+                // bob: 9h
+                closed_issue("bd-4", "bob", "2024-01-01T00:00:00Z", "2024-01-01T09:00:00Z"),
+                // missing closed_at → excluded
+                serde_json::from_value(json!({
+                    "id": "bd-5", "title": "t", "status": "closed",
+                    "created_at": "2024-01-01T00:00:00Z"
+                }))
+                .unwrap(),
+                // open issue → not part of the population at all
+                serde_json::from_value(json!({"id": "bd-6", "title": "t", "status": "open"}))
+                    .unwrap(),
+            ],
+            vec![],
+            vec![],
+        );
+
+        let report = lead_times(&cache);
+        assert_eq!(report.excluded, 1);
+
+        let overall = report.overall.unwrap();
+        assert_eq!(overall.count, 4);
+        // 1h, 3h, 5h, 9h → nearest-rank median is the 2nd value (3h).
+        assert_eq!(overall.median_secs, 3.0 * 3600.0);
+        assert_eq!(overall.p90_secs, 9.0 * 3600.0);
+
+        assert_eq!(report.per_assignee["alice"].median_secs, 3.0 * 3600.0);
+        assert_eq!(report.per_assignee["bob"].count, 1);
+        assert_eq!(report.per_assignee["bob"].mean_secs, 9.0 * 3600.0);
+    }
+
+    #[test]
+    fn empty_cache_yields_no_overall_stats() {
+        let cache = BeadsCache::new();
+        let report = lead_times(&cache);
+        assert!(report.overall.is_none());
+        assert!(report.per_assignee.is_empty());
+        assert_eq!(report.excluded, 0);
+    }
+}
@@ -0,0 +1,28 @@
+//! Integration with the `bd` (beads) CLI.
+//!
+//! All interaction with beads goes through [`BdClient`], which shells out to
+//! the `bd` binary with `--json` and parses the result into the typed
+//! structures in [`types`].
+
+pub mod activity;
+pub mod cache;
+pub mod client;
+pub mod daemon;
+pub mod dag;
+pub mod discovery;
+pub mod export;
+pub mod health;
+pub mod metrics;
+pub mod recommend;
+pub mod status;
+pub mod types;
+
+pub use activity::{ActivityEvent, ActivityStream, StreamConfig};
+pub use cache::{BeadsCache, CacheStats};
+pub use client::{BdClient, BdError, BdResult};
+pub use daemon::DaemonManager;
+pub use discovery::{RegistryEntry, WorkspaceDiscovery};
+pub use dag::{DagBuilder, DagEdge, DagGraph, DagNode, EdgeType};
+pub use health::{HealthChecker, HealthReport};
+pub use status::IssueStatus;
+pub use types::{Comment, DependencyRef, EpicStatus, Gate, Issue, Priority};
@@ -0,0 +1,178 @@
+//! "What should I do next?" recommendations for the assistant panel.
+//!
+//! Pure composition over [`BeadsCache`] reads: nothing here talks to bd.
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::BeadsCache;
+use super::dag::is_issue_in_epic;
+use super::status::{status_category, CATEGORY_IN_PROGRESS};
+use super::types::Issue;
+
+/// In-progress issues untouched for this long count as stalled.
+const STALLED_AFTER_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationKind {
+    ApproveGate,
+    StartIssue,
+    CheckStalled,
+    TriageOrphan,
+}
+
+/// One ranked suggestion: what to act on, why, and a deep link the frontend
+/// routes on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub kind: RecommendationKind,
+    pub target_id: String,
+    pub reason: String,
+    pub link: String,
+}
+
+/// Ranked recommendations, most urgent first: the oldest pending gate, then
+/// the highest-priority ready issue, then stalled in-progress issues, then
+/// orphans with no epic to call home.
+pub fn next_actions(cache: &BeadsCache) -> Vec<Recommendation> {
+    let mut actions = Vec::new();
+
+    let mut pending = cache.get_pending_gates();
+    pending.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    if let Some(gate) = pending.first() {
+        actions.push(Recommendation {
+            kind: RecommendationKind::ApproveGate,
+            target_id: gate.id.clone(),
+            reason: format!(
+                "oldest pending gate, blocking {}",
+                if gate.issue_id.is_empty() { "unknown" } else { &gate.issue_id }
+            ),
+            link: format!("maestro://gate/{}", gate.id),
+        });
+    }
+
+    let mut ready = cache.list_ready();
+    ready.retain(|i| status_category(&i.status) != CATEGORY_IN_PROGRESS);
+    ready.sort_by(|a, b| priority_rank(a).cmp(&priority_rank(b)).then(a.id.cmp(&b.id)));
+    if let Some(issue) = ready.first() {
+        actions.push(Recommendation {
+            kind: RecommendationKind::StartIssue,
+            target_id: issue.id.clone(),
+            reason: "highest-priority issue with all dependencies resolved".to_string(),
+            link: format!("maestro://issue/{}", issue.id),
+        });
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(STALLED_AFTER_DAYS);
+    let mut stalled: Vec<&Issue> = cache
+        .issues_map()
+        .values()
+        .filter(|i| status_category(&i.status) == CATEGORY_IN_PROGRESS)
+        .filter(|i| i.updated_at_ts().is_some_and(|ts| ts < cutoff))
+        .collect();
+    stalled.sort_by(|a, b| a.updated_at_ts().cmp(&b.updated_at_ts()));
+    for issue in stalled {
+        actions.push(Recommendation {
+            kind: RecommendationKind::CheckStalled,
+            target_id: issue.id.clone(),
+            reason: format!("in progress but untouched for over {STALLED_AFTER_DAYS} days"),
+            link: format!("maestro://issue/{}", issue.id),
+        });
+    }
+
+    let epics = cache.list_epics();
+    let mut orphans: Vec<&Issue> = cache
+        .issues_map()
+        .values()
+        .filter(|i| !super::status::status_is_closed(&i.status))
+        .filter(|i| !epics.iter().any(|e| is_issue_in_epic(i, &e.epic_id)))
+        .collect();
+    orphans.sort_by(|a, b| a.id.cmp(&b.id));
+    for issue in orphans {
+        actions.push(Recommendation {
+            kind: RecommendationKind::TriageOrphan,
+            target_id: issue.id.clone(),
+            reason: "belongs to no epic".to_string(),
+            link: format!("maestro://issue/{}", issue.id),
+        });
+    }
+
+    actions
+}
+
+/// Lower rank is more urgent. Accepts bd's numeric priorities and "p0"-style
+/// strings; missing or unreadable priorities sort last. Shared with the
+/// cache's priority sort so both agree on the ordering.
+pub(crate) fn priority_rank(issue: &Issue) -> i64 {
+    match &issue.priority {
+        Some(serde_json::Value::Number(n)) => n.as_i64().unwrap_or(i64::MAX),
+        Some(serde_json::Value::String(s)) => {
+            s.trim_start_matches(['p', 'P']).parse().unwrap_or(i64::MAX)
+        }
+        _ => i64::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn recommendations_come_back_in_priority_order() {
+        let mut cache = BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                // Ready, p2 — beaten by the p0 below.
+                serde_json::from_value(json!({
+                    "id": "bd-e.1", "title": "later", "status": "open", "priority": 2
+                }))
+                .unwrap(),
+                // Ready, p0 — the recommended start.
+                serde_json::from_value(json!({
+                    "id": "bd-e.2", "title": "urgent", "status": "open", "priority": "p0"
+                }))
+                .unwrap(),
+                // Stalled: in progress, last touched long ago.
+                serde_json::from_value(json!({
+                    "id": "bd-e.3", "title": "stuck", "status": "in_progress",
+                    "updated_at": "2024-01-01T00:00:00Z"
+                }))
+                .unwrap(),
+                // Orphan: no epic claims it.
+                serde_json::from_value(json!({
+                    "id": "bd-x", "title": "lost", "status": "open"
+                }))
+                .unwrap(),
+            ],
+            vec![serde_json::from_value(json!({
+                "id": "gate-1", "issue_id": "bd-e.3", "status": "pending",
+                "created_at": "2024-02-01T00:00:00Z"
+            }))
+            .unwrap()],
+            vec![serde_json::from_value(json!({"epic_id": "bd-e", "title": "Epic"})).unwrap()],
+        );
+
+        let actions = next_actions(&cache);
+        let kinds: Vec<RecommendationKind> = actions.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RecommendationKind::ApproveGate,
+                RecommendationKind::StartIssue,
+                RecommendationKind::CheckStalled,
+                RecommendationKind::TriageOrphan,
+            ]
+        );
+        assert_eq!(actions[0].target_id, "gate-1");
+        assert_eq!(actions[1].target_id, "bd-e.2");
+        assert_eq!(actions[2].target_id, "bd-e.3");
+        assert_eq!(actions[3].target_id, "bd-x");
+        assert!(actions[0].link.starts_with("maestro://gate/"));
+    }
+
+    #[test]
+    fn empty_cache_recommends_nothing() {
+        assert!(next_actions(&BeadsCache::new()).is_empty());
+    }
+}
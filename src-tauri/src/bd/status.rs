@@ -0,0 +1,174 @@
+//! Status classification shared by stats, the DAG, and the tray.
+//!
+//! Teams customize bd statuses ("shipped", "wontfix", …), so the built-in
+//! mapping can be extended at runtime with raw-status → canonical-category
+//! overrides via [`set_status_overrides`].
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+pub const CATEGORY_OPEN: &str = "open";
+pub const CATEGORY_IN_PROGRESS: &str = "in_progress";
+pub const CATEGORY_BLOCKED: &str = "blocked";
+pub const CATEGORY_CLOSED: &str = "closed";
+
+/// Canonical status category as a type, for call sites that want to match
+/// exhaustively instead of comparing category strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStatus {
+    Open,
+    InProgress,
+    Blocked,
+    Closed,
+    /// Not a known alias and no override configured. Folded into open
+    /// wherever a bucket is required, but kept distinct so callers can
+    /// surface unrecognized statuses instead of silently mislabeling them.
+    Unknown,
+}
+
+impl IssueStatus {
+    /// Normalize a raw bd status: configured overrides first (matched
+    /// case-insensitively), then the built-in aliases.
+    pub fn from_raw(status: &str) -> Self {
+        let lower = status.to_lowercase();
+        if let Some(category) = overrides().read().unwrap().get(&lower) {
+            return Self::from_category(category);
+        }
+        match lower.as_str() {
+            "open" | "todo" | "backlog" | "ready" => Self::Open,
+            "in_progress" | "in-progress" | "doing" | "active" => Self::InProgress,
+            "blocked" => Self::Blocked,
+            "closed" | "done" | "completed" => Self::Closed,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn from_category(category: &str) -> Self {
+        match category {
+            CATEGORY_IN_PROGRESS => Self::InProgress,
+            CATEGORY_BLOCKED => Self::Blocked,
+            CATEGORY_CLOSED => Self::Closed,
+            _ => Self::Open,
+        }
+    }
+
+    /// The category string this variant corresponds to. `Unknown` reports
+    /// as open, matching how unrecognized statuses have always counted.
+    pub fn category(self) -> &'static str {
+        match self {
+            Self::InProgress => CATEGORY_IN_PROGRESS,
+            Self::Blocked => CATEGORY_BLOCKED,
+            Self::Closed => CATEGORY_CLOSED,
+            Self::Open | Self::Unknown => CATEGORY_OPEN,
+        }
+    }
+
+    pub fn is_closed(self) -> bool {
+        self == Self::Closed
+    }
+}
+
+impl std::str::FromStr for IssueStatus {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_raw(s))
+    }
+}
+
+fn overrides() -> &'static RwLock<HashMap<String, &'static str>> {
+    static OVERRIDES: OnceLock<RwLock<HashMap<String, &'static str>>> = OnceLock::new();
+    OVERRIDES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn canonical(category: &str) -> Option<&'static str> {
+    match category {
+        CATEGORY_OPEN => Some(CATEGORY_OPEN),
+        CATEGORY_IN_PROGRESS => Some(CATEGORY_IN_PROGRESS),
+        CATEGORY_BLOCKED => Some(CATEGORY_BLOCKED),
+        CATEGORY_CLOSED => Some(CATEGORY_CLOSED),
+        _ => None,
+    }
+}
+
+/// Replace the configured overrides. Keys are raw statuses (matched
+/// case-insensitively); values must be one of the four canonical
+/// categories, anything else is dropped with a warning.
+pub fn set_status_overrides(mapping: HashMap<String, String>) {
+    let mut cleaned = HashMap::new();
+    for (raw, category) in mapping {
+        match canonical(&category) {
+            Some(category) => {
+                cleaned.insert(raw.to_lowercase(), category);
+            }
+            None => {
+                tracing::warn!("ignoring status override {raw:?} -> unknown category {category:?}");
+            }
+        }
+    }
+    *overrides().write().unwrap() = cleaned;
+}
+
+/// Canonical category for a raw bd status: configured overrides first, then
+/// the built-in defaults. Unknown statuses count as open.
+pub fn status_category(status: &str) -> &'static str {
+    IssueStatus::from_raw(status).category()
+}
+
+/// Whether a status means "no longer blocking".
+pub fn status_is_closed(status: &str) -> bool {
+    IssueStatus::from_raw(status).is_closed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_status_counts_as_closed_after_configuration() {
+        assert!(!status_is_closed("shipped"));
+        set_status_overrides(
+            [("shipped".to_string(), "closed".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        assert!(status_is_closed("shipped"));
+        assert_eq!(status_category("SHIPPED"), CATEGORY_CLOSED);
+        // Clean up the global for other tests.
+        set_status_overrides(HashMap::new());
+    }
+
+    #[test]
+    fn every_builtin_alias_normalizes_to_its_category() {
+        for raw in ["open", "todo", "backlog", "ready", "OPEN"] {
+            assert_eq!(IssueStatus::from_raw(raw), IssueStatus::Open, "{raw}");
+        }
+        for raw in ["in_progress", "in-progress", "doing", "active"] {
+            assert_eq!(IssueStatus::from_raw(raw), IssueStatus::InProgress, "{raw}");
+        }
+        assert_eq!(IssueStatus::from_raw("blocked"), IssueStatus::Blocked);
+        for raw in ["closed", "done", "completed", "Done"] {
+            assert_eq!(IssueStatus::from_raw(raw), IssueStatus::Closed, "{raw}");
+        }
+        assert_eq!(IssueStatus::from_raw("wontfix"), IssueStatus::Unknown);
+    }
+
+    #[test]
+    fn unknown_statuses_still_count_as_open() {
+        assert_eq!(IssueStatus::Unknown.category(), CATEGORY_OPEN);
+        assert_eq!(status_category("wontfix"), CATEGORY_OPEN);
+        assert!(!IssueStatus::Unknown.is_closed());
+    }
+
+    #[test]
+    fn invalid_override_category_is_ignored() {
+        set_status_overrides(
+            [("weird".to_string(), "nonsense".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(status_category("weird"), CATEGORY_OPEN);
+        set_status_overrides(HashMap::new());
+    }
+}
@@ -0,0 +1,423 @@
+//! Typed views over bd's JSON output.
+//!
+//! bd's schema varies slightly between versions, so every struct keeps a
+//! `#[serde(flatten)] extra` map for fields we don't model explicitly.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A beads issue as returned by `bd list --json` / `bd show --json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub status: String,
+    /// Raw priority as bd reports it (usually an integer 0-4, sometimes a
+    /// string). Kept untyped for forward compatibility.
+    #[serde(default)]
+    pub priority: Option<Value>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Older bd versions report `owner` instead of `assignee`.
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub issue_type: Option<String>,
+    /// Dependencies as bd reports them: either plain ID strings or objects
+    /// with an `id` field (the `bd show` form).
+    #[serde(default)]
+    pub dependencies: Option<Value>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub closed_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Issue {
+    /// The assignee to display: `assignee` when set, falling back to the
+    /// legacy `owner` field.
+    pub fn effective_assignee(&self) -> Option<&str> {
+        self.assignee.as_deref().or(self.owner.as_deref())
+    }
+
+    /// Dependency IDs regardless of whether bd returned them as strings
+    /// (`bd list`) or objects (`bd show`).
+    pub fn dependency_ids(&self) -> Vec<String> {
+        let Some(Value::Array(deps)) = &self.dependencies else {
+            return Vec::new();
+        };
+        deps.iter()
+            .filter_map(|dep| match dep {
+                Value::String(id) => Some(id.clone()),
+                Value::Object(obj) => obj
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(|id| id.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Canonical category for this issue's raw status, honoring any
+    /// configured status overrides. See [`super::status::IssueStatus`].
+    pub fn status_category(&self) -> super::status::IssueStatus {
+        super::status::IssueStatus::from_raw(&self.status)
+    }
+
+    /// Typed view over the raw 0–4 `priority` field: 0 is most urgent.
+    /// Accepts plain numbers and numeric strings ("2", "p2"); anything
+    /// out of range or unreadable is `None`, same as no priority.
+    pub fn priority_level(&self) -> Option<Priority> {
+        let n: i64 = match &self.priority {
+            Some(Value::Number(n)) => n.as_i64()?,
+            Some(Value::String(s)) => {
+                s.trim().trim_start_matches(['p', 'P']).parse().ok()?
+            }
+            _ => return None,
+        };
+        Priority::from_index(n)
+    }
+
+    /// IDs of issues that depend on this one. `bd show` reports these in a
+    /// `dependents` array (strings or objects, like `dependencies`); `bd
+    /// list` omits the field entirely, in which case this is empty.
+    pub fn dependent_ids(&self) -> Vec<String> {
+        let Some(Value::Array(deps)) = self.extra.get("dependents") else {
+            return Vec::new();
+        };
+        deps.iter()
+            .filter_map(|dep| match dep {
+                Value::String(id) => Some(id.clone()),
+                Value::Object(obj) => obj
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(|id| id.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// IDs of non-blocking related issues, read from `extra["related"]` or
+    /// `extra["relates_to"]` (strings or objects, like `dependencies`).
+    /// These carry no blocking semantics — the DAG draws them as soft links.
+    pub fn related_ids(&self) -> Vec<String> {
+        let raw = self
+            .extra
+            .get("related")
+            .or_else(|| self.extra.get("relates_to"));
+        let Some(Value::Array(related)) = raw else {
+            return Vec::new();
+        };
+        related
+            .iter()
+            .filter_map(|rel| match rel {
+                Value::String(id) => Some(id.clone()),
+                Value::Object(obj) => obj
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(|id| id.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Effort estimate, read from `extra["estimate"]` or `extra["points"]`.
+    /// Accepts plain numbers, numeric strings, and strings with a trailing
+    /// unit ("3d", "5pt").
+    pub fn estimate(&self) -> Option<f64> {
+        let raw = self.extra.get("estimate").or_else(|| self.extra.get("points"))?;
+        match raw {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => {
+                let s = s.trim();
+                let numeric: &str = s
+                    .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '-')
+                    .map(|end| &s[..end])
+                    .unwrap_or(s);
+                numeric.parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// `created_at` parsed as RFC 3339; `None` when absent or malformed.
+    pub fn created_at_ts(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        parse_ts(self.created_at.as_deref())
+    }
+
+    /// `closed_at` parsed as RFC 3339; `None` when absent or malformed.
+    pub fn closed_at_ts(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        parse_ts(self.closed_at.as_deref())
+    }
+
+    /// `updated_at` (bd keeps it outside the typed fields) parsed as RFC
+    /// 3339; `None` when absent or malformed.
+    pub fn updated_at_ts(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        parse_ts(self.extra.get("updated_at").and_then(Value::as_str))
+    }
+
+    /// Timestamp of the last recorded activity: `updated_at` when present,
+    /// falling back to the `last_activity` field older bd versions emit.
+    pub fn last_activity_ts(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        self.updated_at_ts()
+            .or_else(|| parse_ts(self.extra.get("last_activity").and_then(Value::as_str)))
+    }
+
+    /// Like [`Issue::dependency_ids`] but preserves the inline `status` and
+    /// `dep_type` that `bd show` attaches to each dependency object. String
+    /// dependencies come back with both set to `None`.
+    pub fn dependencies_detailed(&self) -> Vec<DependencyRef> {
+        let Some(Value::Array(deps)) = &self.dependencies else {
+            return Vec::new();
+        };
+        deps.iter()
+            .filter_map(|dep| match dep {
+                Value::String(id) => Some(DependencyRef {
+                    id: id.clone(),
+                    status: None,
+                    dep_type: None,
+                }),
+                Value::Object(_) => serde_json::from_value(dep.clone()).ok(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn parse_ts(raw: Option<&str>) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    raw.and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+}
+
+/// bd's 0–4 integer priority scale, typed so consumers stop re-interpreting
+/// the raw number. The raw `Issue::priority` field is kept for
+/// forward-compat; use [`Issue::priority_level`] to get this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+    Trivial,
+}
+
+impl Priority {
+    /// Map bd's numeric priority; anything outside 0–4 is `None`.
+    pub fn from_index(n: i64) -> Option<Self> {
+        match n {
+            0 => Some(Self::Critical),
+            1 => Some(Self::High),
+            2 => Some(Self::Medium),
+            3 => Some(Self::Low),
+            4 => Some(Self::Trivial),
+            _ => None,
+        }
+    }
+}
+
+/// A single dependency reference as reported by `bd show`, which inlines the
+/// dependency's status alongside its ID.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyRef {
+    pub id: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub dep_type: Option<String>,
+}
+
+/// An approval gate as returned by `bd gate list --json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gate {
+    pub id: String,
+    #[serde(default)]
+    pub issue_id: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Who approved (or rejected) the gate, when bd reports it.
+    #[serde(default)]
+    pub approved_by: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One comment on an issue, as returned by `bd comment list --json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Per-epic progress rollup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpicStatus {
+    pub epic_id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub total_issues: usize,
+    #[serde(default)]
+    pub closed_issues: usize,
+    #[serde(default)]
+    pub in_progress: usize,
+    #[serde(default)]
+    pub blocked: usize,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn effective_assignee_falls_back_to_owner() {
+        let issue: Issue = serde_json::from_value(json!({
+            "id": "bd-1",
+            "title": "t",
+            "owner": "alice"
+        }))
+        .unwrap();
+        assert_eq!(issue.effective_assignee(), Some("alice"));
+
+        let issue: Issue = serde_json::from_value(json!({
+            "id": "bd-2",
+            "title": "t",
+            "assignee": "bob",
+            "owner": "alice"
+        }))
+        .unwrap();
+        assert_eq!(issue.effective_assignee(), Some("bob"));
+    }
+
+    #[test]
+    fn dependency_ids_handles_strings_and_objects() {
+        let issue: Issue = serde_json::from_value(json!({
+            "id": "bd-1",
+            "title": "t",
+            "dependencies": ["bd-2", {"id": "bd-3", "dep_type": "blocks"}]
+        }))
+        .unwrap();
+        assert_eq!(issue.dependency_ids(), vec!["bd-2", "bd-3"]);
+    }
+
+    #[test]
+    fn priority_level_maps_the_numeric_scale() {
+        let cases = [
+            (json!(0), Some(Priority::Critical)),
+            (json!(1), Some(Priority::High)),
+            (json!(2), Some(Priority::Medium)),
+            (json!(3), Some(Priority::Low)),
+            (json!(4), Some(Priority::Trivial)),
+            (json!("2"), Some(Priority::Medium)),
+            (json!("p1"), Some(Priority::High)),
+            (json!(9), None),
+            (json!("urgent"), None),
+            (json!(null), None),
+        ];
+        for (raw, expected) in cases {
+            let issue: Issue = serde_json::from_value(json!({
+                "id": "bd-1",
+                "title": "t",
+                "priority": raw
+            }))
+            .unwrap();
+            assert_eq!(issue.priority_level(), expected, "priority: {:?}", issue.priority);
+        }
+    }
+
+    #[test]
+    fn estimate_parses_common_encodings() {
+        let cases = [
+            (json!(3), Some(3.0)),
+            (json!(2.5), Some(2.5)),
+            (json!("4"), Some(4.0)),
+            (json!("3d"), Some(3.0)),
+            (json!("5pt"), Some(5.0)),
+            (json!("soon"), None),
+            (json!(null), None),
+        ];
+        for (raw, expected) in cases {
+            let issue: Issue = serde_json::from_value(json!({
+                "id": "bd-1",
+                "title": "t",
+                "estimate": raw
+            }))
+            .unwrap();
+            assert_eq!(issue.estimate(), expected, "estimate {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn estimate_falls_back_to_points() {
+        let issue: Issue = serde_json::from_value(json!({
+            "id": "bd-1",
+            "title": "t",
+            "points": 8
+        }))
+        .unwrap();
+        assert_eq!(issue.estimate(), Some(8.0));
+    }
+
+    #[test]
+    fn gate_round_trips_approved_by() {
+        let gate: Gate = serde_json::from_value(json!({
+            "id": "gate-1",
+            "issue_id": "bd-1",
+            "status": "approved",
+            "approved_by": "alice"
+        }))
+        .unwrap();
+        assert_eq!(gate.approved_by.as_deref(), Some("alice"));
+
+        let value = serde_json::to_value(&gate).unwrap();
+        assert_eq!(value["approved_by"], "alice");
+
+        // Old payloads without the field still deserialize.
+        let gate: Gate = serde_json::from_value(json!({
+            "id": "gate-2",
+            "issue_id": "bd-1",
+            "status": "pending"
+        }))
+        .unwrap();
+        assert_eq!(gate.approved_by, None);
+    }
+
+    #[test]
+    fn dependencies_detailed_preserves_inline_status() {
+        let issue: Issue = serde_json::from_value(json!({
+            "id": "bd-1",
+            "title": "t",
+            "dependencies": [
+                "bd-2",
+                {"id": "bd-3", "status": "closed", "dep_type": "blocks"}
+            ]
+        }))
+        .unwrap();
+        let deps = issue.dependencies_detailed();
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].id, "bd-2");
+        assert_eq!(deps[0].status, None);
+        assert_eq!(deps[1].id, "bd-3");
+        assert_eq!(deps[1].status.as_deref(), Some("closed"));
+        assert_eq!(deps[1].dep_type.as_deref(), Some("blocks"));
+    }
+}
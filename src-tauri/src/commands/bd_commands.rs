@@ -0,0 +1,1262 @@
+//! Tauri commands bridging the frontend to [`BdClient`] and [`BeadsCache`].
+//!
+//! Commands stay thin: argument plumbing, the bd/cache call, and the event
+//! emission. Domain logic lives in the `bd` module.
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::bd::cache::{CacheAge, CacheStats};
+use crate::bd::{Comment, DagBuilder, DagGraph, EpicStatus, Gate, HealthReport, Issue};
+use crate::commands::CommandError;
+use crate::events::{DashboardEvent, DASHBOARD_EVENT_CHANNEL};
+use crate::state::AppState;
+
+/// Pull fresh data from bd and replace the cache contents, announcing the
+/// finished sync on the dashboard channel when an [`AppHandle`] is around.
+/// Shared with the periodic refresh task in `lib.rs`.
+pub(crate) async fn refresh_from_bd(
+    app: Option<&AppHandle>,
+    state: &AppState,
+) -> Result<CacheStats, CommandError> {
+    let started = std::time::Instant::now();
+    let client = state.bd_client().await;
+    let (issues, gates, epics) =
+        tokio::join!(client.list_issues(), client.list_gates(), client.list_epics());
+    let issues = issues.map_err(CommandError::from)?;
+    let gates = gates.map_err(CommandError::from)?;
+    // Older bd versions have no epics subcommand; refresh the rest anyway.
+    let epics = epics.unwrap_or_default();
+    let mut cache = state.beads_cache.write().await;
+    cache.full_refresh(issues, gates, epics);
+    let stats = cache.get_stats();
+    drop(cache);
+
+    if let Some(app) = app {
+        emit_dashboard(
+            app,
+            &DashboardEvent::CacheRefreshed(refresh_summary(&stats, started.elapsed())),
+        );
+    }
+    Ok(stats)
+}
+
+/// The human-readable payload carried by the post-refresh `CacheRefreshed`
+/// event, e.g. `"issues: 42, gates: 3, duration: 120ms"`.
+fn refresh_summary(stats: &CacheStats, elapsed: std::time::Duration) -> String {
+    format!(
+        "issues: {}, gates: {}, duration: {}ms",
+        stats.total_issues,
+        stats.gates,
+        elapsed.as_millis()
+    )
+}
+
+fn emit_dashboard(app: &AppHandle, event: &DashboardEvent) {
+    if let Err(err) = app.emit(DASHBOARD_EVENT_CHANNEL, event) {
+        tracing::warn!("failed to emit dashboard event: {err}");
+    }
+}
+
+/// A read response that may have been served from the local cache while bd
+/// was unreachable. `stale: true` is the frontend's cue to show the offline
+/// banner next to the data instead of a blank error state.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CacheBacked<T> {
+    pub data: T,
+    pub stale: bool,
+}
+
+/// Errors that mean "bd is unreachable" rather than "the request was bad".
+/// Only these justify serving cached data; an invalid argument or a parse
+/// failure would be the same against the cache.
+fn is_offline_error(err: &crate::bd::BdError) -> bool {
+    matches!(
+        err,
+        crate::bd::BdError::CliNotFound { .. }
+            | crate::bd::BdError::CommandFailed { .. }
+            | crate::bd::BdError::Timeout(_)
+    )
+}
+
+/// Serve a read from bd, falling back to `cached` when bd is unreachable.
+/// The fallback only masks the error when the cache actually has something
+/// to offer — an empty cache surfaces the original failure.
+fn fallback_to_cache<T>(
+    result: crate::bd::BdResult<T>,
+    cached: Option<T>,
+) -> Result<CacheBacked<T>, CommandError> {
+    match result {
+        Ok(data) => Ok(CacheBacked { data, stale: false }),
+        Err(err) if is_offline_error(&err) => match cached {
+            Some(data) => {
+                tracing::warn!("bd unreachable ({err}); serving the cached copy");
+                Ok(CacheBacked { data, stale: true })
+            }
+            None => Err(err.into()),
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Flip the offline flag to match the latest read outcome and announce the
+/// transition (once per flip, not per command) on the dashboard channel.
+fn note_connectivity(app: &AppHandle, state: &AppState, offline: bool) {
+    if state.set_offline(offline) {
+        emit_dashboard(
+            app,
+            &DashboardEvent::ConnectionChanged {
+                connected: !offline,
+            },
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn list_issues(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CacheBacked<Vec<Issue>>, CommandError> {
+    let result = state.bd_client().await.list_issues().await;
+    let cached = if result.is_err() {
+        let issues = state.beads_cache.read().await.list_issues();
+        (!issues.is_empty()).then_some(issues)
+    } else {
+        None
+    };
+    let response = fallback_to_cache(result, cached)?;
+    note_connectivity(&app, &state, response.stale);
+    Ok(response)
+}
+
+#[tauri::command]
+pub async fn list_issues_paged(
+    state: State<'_, AppState>,
+    offset: usize,
+    limit: usize,
+) -> Result<crate::bd::client::IssuePage, CommandError> {
+    state
+        .bd_client()
+        .await
+        .list_issues_paged(offset, limit)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn list_issues_filtered(
+    state: State<'_, AppState>,
+    status: Option<String>,
+    assignee: Option<String>,
+    label: Option<String>,
+) -> Result<Vec<Issue>, CommandError> {
+    state
+        .bd_client()
+        .await
+        .list_issues_filtered(status.as_deref(), assignee.as_deref(), label.as_deref())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<CacheBacked<Issue>, CommandError> {
+    let result = state.bd_client().await.get_issue(&issue_id).await;
+    let cached = if result.is_err() {
+        state.beads_cache.read().await.get_issue(&issue_id).cloned()
+    } else {
+        None
+    };
+    let response = fallback_to_cache(result, cached)?;
+    note_connectivity(&app, &state, response.stale);
+    Ok(response)
+}
+
+#[tauri::command]
+pub async fn create_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    title: String,
+    description: Option<String>,
+    labels: Option<Vec<String>>,
+    parent: Option<String>,
+    deps: Option<Vec<String>>,
+    assignee: Option<String>,
+    priority: Option<u8>,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .create_issue(
+            &title,
+            description.as_deref(),
+            &labels.unwrap_or_default(),
+            parent.as_deref(),
+            &deps.unwrap_or_default(),
+            assignee.as_deref(),
+            priority,
+        )
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn update_issue_status(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    status: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .update_issue_status(&issue_id, &status)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn assign_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    assignee: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .assign_issue(&issue_id, &assignee)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn unassign_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .unassign_issue(&issue_id)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn close_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .close_issue(&issue_id)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn reopen_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .reopen_issue(&issue_id)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn delete_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<serde_json::Value, CommandError> {
+    let result = state
+        .bd_client()
+        .await
+        .delete_issue(&issue_id)
+        .await
+        .map_err(CommandError::from)?;
+    // Remove proactively so the UI updates without waiting for the next
+    // activity event.
+    state.beads_cache.write().await.remove_issue(&issue_id);
+    emit_dashboard(
+        &app,
+        &DashboardEvent::CacheRefreshed(format!("issue {issue_id} deleted")),
+    );
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn claim_issue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    assignee: Option<String>,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .claim_issue(&issue_id, assignee.as_deref())
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+/// Outcome of a bulk status update: issues that moved plus per-issue failure
+/// messages for the rest.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BulkUpdateOutcome {
+    pub updated: Vec<Issue>,
+    pub failures: Vec<BulkUpdateFailure>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BulkUpdateFailure {
+    pub issue_id: String,
+    pub error: String,
+}
+
+/// Move several issues to `status` at once, emitting one aggregated
+/// `CacheRefreshed` event instead of one `IssueUpdated` per issue.
+#[tauri::command]
+pub async fn bulk_update_status(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_ids: Vec<String>,
+    status: String,
+) -> Result<BulkUpdateOutcome, CommandError> {
+    let ids: Vec<&str> = issue_ids.iter().map(String::as_str).collect();
+    let results = state
+        .bd_client()
+        .await
+        .bulk_update_status(&ids, &status)
+        .await
+        .map_err(CommandError::from)?;
+
+    let mut outcome = BulkUpdateOutcome {
+        updated: Vec::new(),
+        failures: Vec::new(),
+    };
+    let mut cache = state.beads_cache.write().await;
+    for (id, result) in issue_ids.iter().zip(results) {
+        match result {
+            Ok(issue) => {
+                cache.upsert_issue(issue.clone());
+                outcome.updated.push(issue);
+            }
+            Err(err) => outcome.failures.push(BulkUpdateFailure {
+                issue_id: id.clone(),
+                error: err.to_string(),
+            }),
+        }
+    }
+    drop(cache);
+
+    emit_dashboard(
+        &app,
+        &DashboardEvent::CacheRefreshed(format!(
+            "bulk status update: {}/{} moved to {status}",
+            outcome.updated.len(),
+            issue_ids.len()
+        )),
+    );
+    Ok(outcome)
+}
+
+#[tauri::command]
+pub async fn add_label(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    label: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .add_label(&issue_id, &label)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn remove_label(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    label: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .remove_label(&issue_id, &label)
+        .await
+        .map_err(CommandError::from)?;
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn add_dependency(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    depends_on: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .add_dependency(&issue_id, &depends_on)
+        .await
+        .map_err(CommandError::from)?;
+    // Upsert so the next get_dag sees the new edge immediately.
+    state.beads_cache.write().await.upsert_issue(issue.clone());
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn remove_dependency(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    issue_id: String,
+    depends_on: String,
+) -> Result<Issue, CommandError> {
+    let issue = state
+        .bd_client()
+        .await
+        .remove_dependency(&issue_id, &depends_on)
+        .await
+        .map_err(CommandError::from)?;
+    state.beads_cache.write().await.upsert_issue(issue.clone());
+    emit_dashboard(&app, &DashboardEvent::IssueUpdated(issue.clone()));
+    Ok(issue)
+}
+
+#[tauri::command]
+pub async fn get_comments(
+    state: State<'_, AppState>,
+    issue_id: String,
+) -> Result<Vec<Comment>, CommandError> {
+    state
+        .bd_client()
+        .await
+        .get_comments(&issue_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn add_comment(
+    state: State<'_, AppState>,
+    issue_id: String,
+    body: String,
+) -> Result<Comment, CommandError> {
+    state
+        .bd_client()
+        .await
+        .add_comment(&issue_id, &body)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn set_default_assignee(
+    state: State<'_, AppState>,
+    assignee: Option<String>,
+) -> Result<(), CommandError> {
+    state.bd_client().await.set_default_assignee(assignee);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_gates(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CacheBacked<Vec<Gate>>, CommandError> {
+    let result = state.bd_client().await.list_gates().await;
+    let cached = if result.is_err() {
+        let gates = state.beads_cache.read().await.gates();
+        (!gates.is_empty()).then_some(gates)
+    } else {
+        None
+    };
+    let response = fallback_to_cache(result, cached)?;
+    note_connectivity(&app, &state, response.stale);
+    Ok(response)
+}
+
+/// Authoritative single-gate fetch, used to confirm a gate's state after a
+/// resolve rather than trusting the resolve response.
+#[tauri::command]
+pub async fn get_gate(state: State<'_, AppState>, gate_id: String) -> Result<Gate, CommandError> {
+    state
+        .bd_client()
+        .await
+        .get_gate(&gate_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn resolve_gate(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    gate_id: String,
+    reason: String,
+) -> Result<Gate, CommandError> {
+    let gate = state
+        .bd_client()
+        .await
+        .resolve_gate(&gate_id, &reason)
+        .await
+        .map_err(CommandError::from)?;
+    let mut cache = state.beads_cache.write().await;
+    cache.upsert_gate(gate.clone());
+    let pending = cache.get_pending_gates().len();
+    drop(cache);
+    crate::tray::refresh_badge(&app, pending);
+    emit_dashboard(&app, &DashboardEvent::GateResolved(gate.clone()));
+    Ok(gate)
+}
+
+/// Reject a gate with a reason. Same cache/badge/event plumbing as
+/// [`resolve_gate`]; the rejected status flows out in the `GateResolved`
+/// payload so the frontend sees the outcome.
+#[tauri::command]
+pub async fn reject_gate(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    gate_id: String,
+    reason: String,
+) -> Result<Gate, CommandError> {
+    let gate = state
+        .bd_client()
+        .await
+        .reject_gate(&gate_id, &reason)
+        .await
+        .map_err(CommandError::from)?;
+    let mut cache = state.beads_cache.write().await;
+    cache.upsert_gate(gate.clone());
+    let pending = cache.get_pending_gates().len();
+    drop(cache);
+    crate::tray::refresh_badge(&app, pending);
+    emit_dashboard(&app, &DashboardEvent::GateResolved(gate.clone()));
+    Ok(gate)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BulkGateOutcome {
+    pub resolved: Vec<Gate>,
+    pub failures: Vec<BulkGateFailure>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BulkGateFailure {
+    pub gate_id: String,
+    pub error: String,
+}
+
+/// Approve several gates with one shared reason, emitting one aggregated
+/// `CacheRefreshed` event instead of one `GateResolved` per gate. Partial
+/// failures land in the outcome rather than aborting the batch.
+#[tauri::command]
+pub async fn bulk_resolve_gates(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    gate_ids: Vec<String>,
+    reason: String,
+) -> Result<BulkGateOutcome, CommandError> {
+    let ids: Vec<&str> = gate_ids.iter().map(String::as_str).collect();
+    let results = state
+        .bd_client()
+        .await
+        .resolve_gates(&ids, &reason)
+        .await
+        .map_err(CommandError::from)?;
+
+    let mut outcome = BulkGateOutcome {
+        resolved: Vec::new(),
+        failures: Vec::new(),
+    };
+    let mut cache = state.beads_cache.write().await;
+    for (id, result) in gate_ids.iter().zip(results) {
+        match result {
+            Ok(gate) => {
+                cache.upsert_gate(gate.clone());
+                outcome.resolved.push(gate);
+            }
+            Err(err) => outcome.failures.push(BulkGateFailure {
+                gate_id: id.clone(),
+                error: err.to_string(),
+            }),
+        }
+    }
+    let pending = cache.get_pending_gates().len();
+    drop(cache);
+
+    crate::tray::refresh_badge(&app, pending);
+    emit_dashboard(
+        &app,
+        &DashboardEvent::CacheRefreshed(format!(
+            "bulk gate resolve: {}/{} approved",
+            outcome.resolved.len(),
+            gate_ids.len()
+        )),
+    );
+    Ok(outcome)
+}
+
+/// One row of the workload table: [`crate::bd::cache::WorkloadStats`]
+/// flattened alongside its assignee for the frontend.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorkloadEntry {
+    pub assignee: String,
+    pub open: usize,
+    pub in_progress: usize,
+    pub total: usize,
+}
+
+/// Open work per assignee, heaviest first (ties broken by name so the
+/// order is stable across refreshes).
+#[tauri::command]
+pub async fn get_workload(state: State<'_, AppState>) -> Result<Vec<WorkloadEntry>, CommandError> {
+    let mut entries: Vec<WorkloadEntry> = state
+        .beads_cache
+        .read()
+        .await
+        .workload()
+        .into_iter()
+        .map(|(assignee, stats)| WorkloadEntry {
+            assignee,
+            open: stats.open,
+            in_progress: stats.in_progress,
+            total: stats.total,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.assignee.cmp(&b.assignee)));
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn get_stats(state: State<'_, AppState>) -> Result<CacheStats, CommandError> {
+    Ok(state.beads_cache.read().await.get_stats())
+}
+
+/// Pull everything from bd and replace the cache contents — the glue that
+/// actually populates the cache the read commands serve from.
+#[tauri::command]
+pub async fn refresh_cache(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CacheStats, CommandError> {
+    refresh_from_bd(Some(&app), &state).await
+}
+
+/// Configure raw-status → canonical-category overrides (e.g. "shipped" →
+/// "closed") consulted by stats and the DAG.
+#[tauri::command]
+pub async fn set_status_mapping(
+    mapping: std::collections::HashMap<String, String>,
+) -> Result<(), CommandError> {
+    crate::bd::status::set_status_overrides(mapping);
+    Ok(())
+}
+
+/// Ranked "what next" recommendations for the assistant panel.
+#[tauri::command]
+pub async fn get_next_action(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::bd::recommend::Recommendation>, CommandError> {
+    Ok(crate::bd::recommend::next_actions(
+        &*state.beads_cache.read().await,
+    ))
+}
+
+/// Lead-time (created → closed) flow stats, overall and per assignee.
+#[tauri::command]
+pub async fn get_lead_times(
+    state: State<'_, AppState>,
+) -> Result<crate::bd::metrics::LeadTimeReport, CommandError> {
+    Ok(crate::bd::metrics::lead_times(
+        &*state.beads_cache.read().await,
+    ))
+}
+
+/// Tiny, frequently polled endpoint backing the "last synced N seconds ago"
+/// indicator; deliberately avoids serializing full stats.
+#[tauri::command]
+pub async fn get_cache_age_secs(state: State<'_, AppState>) -> Result<CacheAge, CommandError> {
+    let cache = state.beads_cache.read().await;
+    Ok(CacheAge {
+        age_secs: cache.age_secs(),
+        is_stale: cache.is_stale(),
+    })
+}
+
+/// Runtime-tunable freshness thresholds, in seconds.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StalenessConfig {
+    /// How long after a sync the cache reports itself stale.
+    pub cache_stale_secs: u64,
+    /// Cache age past which the health report flags the workspace.
+    pub health_max_age_secs: u64,
+}
+
+#[tauri::command]
+pub async fn get_staleness_config(
+    state: State<'_, AppState>,
+) -> Result<StalenessConfig, CommandError> {
+    let cache_stale_secs = state.beads_cache.read().await.stale_after().as_secs();
+    let health_max_age_secs = state.health_checker.read().await.max_cache_age().as_secs();
+    Ok(StalenessConfig {
+        cache_stale_secs,
+        health_max_age_secs,
+    })
+}
+
+/// Adjust either threshold at runtime; omitted fields keep their current
+/// value. Returns the resulting configuration.
+#[tauri::command]
+pub async fn set_staleness_config(
+    state: State<'_, AppState>,
+    cache_stale_secs: Option<u64>,
+    health_max_age_secs: Option<u64>,
+) -> Result<StalenessConfig, CommandError> {
+    if let Some(secs) = cache_stale_secs {
+        state
+            .beads_cache
+            .write()
+            .await
+            .set_stale_after(std::time::Duration::from_secs(secs));
+    }
+    if let Some(secs) = health_max_age_secs {
+        state
+            .health_checker
+            .write()
+            .await
+            .set_max_cache_age(std::time::Duration::from_secs(secs));
+    }
+    get_staleness_config(state).await
+}
+
+/// Run the workspace health probes and return the bundled report.
+#[tauri::command]
+pub async fn check_health(state: State<'_, AppState>) -> Result<HealthReport, CommandError> {
+    let client = state.bd_client().await;
+    let cache = state.beads_cache.read().await;
+    // Write lock: each run is recorded in the checker's history ring.
+    let mut checker = state.health_checker.write().await;
+    Ok(checker.run(&client, &cache).await)
+}
+
+/// Just the install/daemon dimensions, separated so the UI can tell the
+/// user to install bd versus merely start the daemon.
+#[tauri::command]
+pub async fn get_bd_health(
+    state: State<'_, AppState>,
+) -> Result<crate::bd::health::BdHealth, CommandError> {
+    let client = state.bd_client().await;
+    Ok(crate::bd::health::probe_bd(&client).await)
+}
+
+/// Recent health outcomes, oldest first, for the uptime sparkline.
+#[tauri::command]
+pub async fn get_health_history(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::bd::health::HealthStatus>, CommandError> {
+    Ok(state.health_checker.read().await.history())
+}
+
+#[tauri::command]
+pub async fn search_issues(
+    state: State<'_, AppState>,
+    query: String,
+) -> Result<Vec<Issue>, CommandError> {
+    Ok(state.beads_cache.read().await.search_issues(&query))
+}
+
+/// Cache-side sorted listing so the UI doesn't re-sort huge workspaces on
+/// every render. `by` is one of id/title/status/priority/assignee.
+#[tauri::command]
+pub async fn list_issues_sorted(
+    state: State<'_, AppState>,
+    by: String,
+    desc: bool,
+) -> Result<Vec<Issue>, CommandError> {
+    let key: crate::bd::cache::SortKey = by.parse()?;
+    Ok(state.beads_cache.read().await.list_issues_sorted(key, desc))
+}
+
+#[tauri::command]
+pub async fn search_issues_advanced(
+    state: State<'_, AppState>,
+    query: String,
+    labels: Option<Vec<String>>,
+    status: Option<String>,
+    assignee: Option<String>,
+) -> Result<Vec<Issue>, CommandError> {
+    Ok(state.beads_cache.read().await.search_issues_advanced(
+        &query,
+        &labels.unwrap_or_default(),
+        status.as_deref(),
+        assignee.as_deref(),
+    ))
+}
+
+#[tauri::command]
+pub async fn list_ready(state: State<'_, AppState>) -> Result<Vec<Issue>, CommandError> {
+    Ok(state.beads_cache.read().await.list_ready())
+}
+
+#[tauri::command]
+pub async fn list_blocked(state: State<'_, AppState>) -> Result<Vec<Issue>, CommandError> {
+    Ok(state.beads_cache.read().await.list_blocked())
+}
+
+/// Open issues with no recorded activity in the last `days` days.
+#[tauri::command]
+pub async fn get_stale_issues(
+    state: State<'_, AppState>,
+    days: u64,
+) -> Result<Vec<Issue>, CommandError> {
+    let older_than = std::time::Duration::from_secs(days * 24 * 60 * 60);
+    Ok(state.beads_cache.read().await.stale_issues(older_than))
+}
+
+#[tauri::command]
+pub async fn get_pending_gates(state: State<'_, AppState>) -> Result<Vec<Gate>, CommandError> {
+    Ok(state.beads_cache.read().await.get_pending_gates())
+}
+
+#[tauri::command]
+pub async fn get_epic_status(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<Option<EpicStatus>, CommandError> {
+    Ok(state.beads_cache.read().await.get_epic_status(&epic_id).cloned())
+}
+
+#[tauri::command]
+pub async fn list_epics(state: State<'_, AppState>) -> Result<Vec<EpicStatus>, CommandError> {
+    Ok(state.beads_cache.read().await.list_epics())
+}
+
+/// Markdown status report for an epic. The frontend decides whether to
+/// display it or save it via the fs plugin.
+#[tauri::command]
+pub async fn export_epic_markdown(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<String, CommandError> {
+    let cache = state.beads_cache.read().await;
+    crate::bd::export::epic_markdown(&cache, &epic_id)
+        .ok_or_else(|| CommandError::new("not_found", format!("unknown epic: {epic_id}")))
+}
+
+/// Dump every cached issue to `path` as JSON Lines, for backups and
+/// external analysis. Returns the number of lines written.
+#[tauri::command]
+pub async fn export_issues_jsonl(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<usize, CommandError> {
+    let issues = state.beads_cache.read().await.list_issues();
+    crate::bd::export::issues_jsonl(issues, std::path::Path::new(&path))
+        .await
+        .map_err(|err| CommandError::new("io_error", format!("cannot write {path}: {err}")))
+}
+
+/// Dump every cached issue to `path` as CSV for spreadsheet consumers.
+/// Returns the number of data rows written.
+#[tauri::command]
+pub async fn export_issues_csv(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<usize, CommandError> {
+    let issues = state.beads_cache.read().await.list_issues();
+    crate::bd::export::issues_csv(issues, std::path::Path::new(&path))
+        .await
+        .map_err(|err| CommandError::new("io_error", format!("cannot write {path}: {err}")))
+}
+
+#[tauri::command]
+pub async fn get_dag(
+    state: State<'_, AppState>,
+    epic_id: String,
+    status_filter: Option<Vec<String>>,
+) -> Result<DagGraph, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    Ok(DagBuilder::new(cache.issues_map(), &gates).build_dag(&epic_id, status_filter.as_deref()))
+}
+
+/// Whether an epic's dependency graph contains a cycle, so the UI can flag
+/// it before attempting a layout that assumes acyclicity.
+#[tauri::command]
+pub async fn has_cycles(state: State<'_, AppState>, epic_id: String) -> Result<bool, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    let graph = DagBuilder::new(cache.issues_map(), &gates).build_dag(&epic_id, None);
+    Ok(!graph.detect_cycles().is_empty())
+}
+
+/// Graphviz DOT rendering of an epic's DAG, for users who want to run it
+/// through external layout tooling.
+#[tauri::command]
+pub async fn export_dag_dot(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<String, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    Ok(DagBuilder::new(cache.issues_map(), &gates)
+        .build_dag(&epic_id, None)
+        .to_dot())
+}
+
+/// A DAG plus the completion figures the UI would otherwise recount itself.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DagWithSummary {
+    pub graph: DagGraph,
+    /// Closed fraction of the epic's issue nodes, 0.0–1.0.
+    pub progress: f32,
+}
+
+#[tauri::command]
+pub async fn get_dag_with_summary(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<DagWithSummary, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    let graph = DagBuilder::new(cache.issues_map(), &gates).build_dag(&epic_id, None);
+    let progress = graph.progress();
+    Ok(DagWithSummary { graph, progress })
+}
+
+/// Everything the epic page renders, assembled in one cache read instead of
+/// three separate commands (and three lock acquisitions).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EpicOverview {
+    pub status: Option<EpicStatus>,
+    pub dag: DagGraph,
+    /// Pending gates attached to issues inside this epic.
+    pub pending_gates: Vec<Gate>,
+    pub critical_path_len: usize,
+}
+
+pub(crate) fn build_epic_overview(cache: &crate::bd::BeadsCache, epic_id: &str) -> EpicOverview {
+    let gates = cache.gates();
+    let dag = DagBuilder::new(cache.issues_map(), &gates).build_dag(epic_id, None);
+    let node_ids: std::collections::HashSet<&str> =
+        dag.nodes.iter().map(|n| n.id.as_str()).collect();
+    let pending_gates = cache
+        .get_pending_gates()
+        .into_iter()
+        .filter(|gate| node_ids.contains(gate.issue_id.as_str()))
+        .collect();
+    let critical_path_len = dag.critical_path().len();
+    EpicOverview {
+        status: cache.get_epic_status(epic_id).cloned(),
+        dag,
+        pending_gates,
+        critical_path_len,
+    }
+}
+
+#[tauri::command]
+pub async fn get_epic_overview(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<EpicOverview, CommandError> {
+    Ok(build_epic_overview(
+        &*state.beads_cache.read().await,
+        &epic_id,
+    ))
+}
+
+/// Subgraph around one issue: its ancestors and descendants up to `depth`
+/// dependency hops, regardless of epic.
+#[tauri::command]
+pub async fn get_issue_neighborhood(
+    state: State<'_, AppState>,
+    issue_id: String,
+    depth: usize,
+) -> Result<DagGraph, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    Ok(DagBuilder::new(cache.issues_map(), &gates).build_neighborhood(&issue_id, depth))
+}
+
+/// Ordered node IDs on the longest dependency chain gating epic completion.
+#[tauri::command]
+pub async fn get_critical_path(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<Vec<String>, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    Ok(DagBuilder::new(cache.issues_map(), &gates)
+        .build_dag(&epic_id, None)
+        .critical_path())
+}
+
+/// Mermaid flowchart rendering of an epic's DAG, for pasting straight into
+/// Markdown docs.
+#[tauri::command]
+pub async fn export_dag_mermaid(
+    state: State<'_, AppState>,
+    epic_id: String,
+) -> Result<String, CommandError> {
+    let cache = state.beads_cache.read().await;
+    let gates = cache.gates();
+    Ok(DagBuilder::new(cache.issues_map(), &gates)
+        .build_dag(&epic_id, None)
+        .to_mermaid())
+}
+
+#[tauri::command]
+pub async fn switch_workspace(
+    state: State<'_, AppState>,
+    workspace: String,
+) -> Result<(), CommandError> {
+    state
+        .switch_bd_client(workspace.into())
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Workspaces bd knows about, per the global registry, with their on-disk
+/// state.
+#[tauri::command]
+pub async fn list_workspaces() -> Result<Vec<crate::bd::discovery::WorkspaceInfo>, CommandError> {
+    crate::bd::WorkspaceDiscovery::new()
+        .ok_or_else(|| {
+            CommandError::new("environment", "no home directory to locate the beads registry")
+        })?
+        .discover()
+        .map_err(CommandError::from)
+}
+
+/// Register a directory with bd (`bd init`) and hand back the refreshed
+/// workspace list so the switcher can show the newcomer immediately.
+#[tauri::command]
+pub async fn register_workspace(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<Vec<crate::bd::discovery::WorkspaceInfo>, CommandError> {
+    let path = std::path::PathBuf::from(path);
+    if !path.is_dir() {
+        return Err(CommandError::new(
+            "invalid_argument",
+            format!("not a directory: {}", path.display()),
+        ));
+    }
+    state
+        .bd_client()
+        .await
+        .init_workspace(&path)
+        .await
+        .map_err(CommandError::from)?;
+    list_workspaces().await
+}
+
+/// Stop-then-start the bd daemon for the current workspace; the escape
+/// hatch for a wedged daemon. Returns bd's post-start status payload.
+#[tauri::command]
+pub async fn restart_bd_daemon(state: State<'_, AppState>) -> Result<serde_json::Value, CommandError> {
+    let client = state.bd_client().await;
+    let manager = crate::bd::DaemonManager::with_binary(client.bd_path(), client.workspace());
+    manager.restart().await.map_err(CommandError::from)
+}
+
+/// Whether reads are currently being served from the cache because bd is
+/// unreachable; the frontend polls this for its offline banner (the
+/// `ConnectionChanged` event covers transitions in between).
+#[tauri::command]
+pub async fn is_offline(state: State<'_, AppState>) -> Result<bool, CommandError> {
+    Ok(state.is_offline())
+}
+
+/// Stop applying activity events without tearing the stream down; useful
+/// during bulk operations.
+#[tauri::command]
+pub async fn pause_activity(state: State<'_, AppState>) -> Result<(), CommandError> {
+    state.pause_activity();
+    Ok(())
+}
+
+/// Resume event processing. If the stream was actually paused, events were
+/// dropped, so reconcile with a full refresh.
+#[tauri::command]
+pub async fn resume_activity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<CacheStats>, CommandError> {
+    if state.resume_activity() {
+        refresh_from_bd(Some(&app), &state).await.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Tail of this session's activity stream, newest first. `limit` caps the
+/// result; the buffer itself holds at most
+/// [`crate::state::ACTIVITY_LOG_CAPACITY`] events.
+#[tauri::command]
+pub async fn get_recent_activity(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<crate::bd::ActivityEvent>, CommandError> {
+    let limit = limit.unwrap_or(crate::state::ACTIVITY_LOG_CAPACITY);
+    Ok(state.activity_log.read().await.recent(limit))
+}
+
+/// Subscribe the frontend to targeted `issue-watch-update` events for one
+/// issue. See `process_activity_event` in `state.rs`.
+#[tauri::command]
+pub async fn watch_issue(state: State<'_, AppState>, issue_id: String) -> Result<(), CommandError> {
+    state.watched_issues.write().await.insert(issue_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unwatch_issue(state: State<'_, AppState>, issue_id: String) -> Result<(), CommandError> {
+    state.watched_issues.write().await.remove(&issue_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_summary_reports_counts_and_duration() {
+        let stats = CacheStats {
+            total_issues: 42,
+            open: 40,
+            in_progress: 1,
+            blocked: 0,
+            closed: 1,
+            gates: 3,
+            pending_gates: 2,
+            epics: 1,
+            last_sync: Some(0),
+            id_collisions: 0,
+        };
+        let summary = refresh_summary(&stats, std::time::Duration::from_millis(120));
+        assert_eq!(summary, "issues: 42, gates: 3, duration: 120ms");
+    }
+
+    #[test]
+    fn epic_overview_assembles_every_field_in_one_pass() {
+        let mut cache = crate::bd::BeadsCache::new();
+        cache.full_refresh(
+            vec![
+                issue_from(serde_json::json!({
+                    "id": "bd-e.1", "title": "base", "status": "open"
+                })),
+                issue_from(serde_json::json!({
+                    "id": "bd-e.2", "title": "next", "status": "open",
+                    "dependencies": ["bd-e.1"]
+                })),
+                issue_from(serde_json::json!({
+                    "id": "bd-x", "title": "elsewhere", "status": "open"
+                })),
+            ],
+            vec![
+                serde_json::from_value(serde_json::json!({
+                    "id": "gate-1", "issue_id": "bd-e.1", "status": "pending"
+                }))
+                .unwrap(),
+                serde_json::from_value(serde_json::json!({
+                    "id": "gate-2", "issue_id": "bd-x", "status": "pending"
+                }))
+                .unwrap(),
+            ],
+            vec![serde_json::from_value(serde_json::json!({
+                "epic_id": "bd-e", "title": "Epic"
+            }))
+            .unwrap()],
+        );
+
+        let overview = build_epic_overview(&cache, "bd-e");
+        assert!(overview.status.is_some());
+        assert!(overview.dag.nodes.len() >= 2);
+        assert_eq!(overview.pending_gates.len(), 1, "only this epic's gates");
+        assert_eq!(overview.pending_gates[0].id, "gate-1");
+        assert!(overview.critical_path_len >= 2);
+    }
+
+    fn issue_from(value: serde_json::Value) -> Issue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn issue(id: &str) -> Issue {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "title": "t", "status": "open"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn successful_reads_are_never_tagged_stale() {
+        let response = fallback_to_cache(Ok(vec![issue("bd-1")]), Some(vec![issue("bd-2")]));
+        let response = response.unwrap();
+        assert!(!response.stale);
+        assert_eq!(response.data[0].id, "bd-1");
+    }
+
+    #[test]
+    fn unreachable_bd_falls_back_to_the_cache() {
+        let err = crate::bd::BdError::CliNotFound {
+            checked_paths: vec!["/usr/local/bin/bd".to_string()],
+        };
+        let response = fallback_to_cache(Err(err), Some(vec![issue("bd-1")])).unwrap();
+        assert!(response.stale);
+        assert_eq!(response.data[0].id, "bd-1");
+    }
+
+    #[test]
+    fn empty_cache_surfaces_the_original_failure() {
+        let err = crate::bd::BdError::CommandFailed {
+            stderr: "daemon not running".to_string(),
+        };
+        let result = fallback_to_cache::<Vec<Issue>>(Err(err), None);
+        let err = result.unwrap_err();
+        assert_eq!(err.code, "command_failed");
+        assert!(err.message.contains("daemon not running"));
+    }
+
+    #[test]
+    fn non_connectivity_errors_skip_the_fallback() {
+        let err = crate::bd::BdError::InvalidArgument("bad id".to_string());
+        let result = fallback_to_cache(Err(err), Some(vec![issue("bd-1")]));
+        assert!(result.is_err(), "a usage error is not an offline condition");
+    }
+}
@@ -0,0 +1,48 @@
+//! Tauri command handlers.
+
+pub mod bd_commands;
+
+use serde::Serialize;
+
+use crate::bd::BdError;
+
+/// The error shape every command returns: a stable `code` the frontend can
+/// branch on for remediation (install bd, start the daemon, fix the input),
+/// plus the human-readable message for display.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl From<BdError> for CommandError {
+    fn from(err: BdError) -> Self {
+        Self {
+            code: err.code(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Plain-string errors (including `FromStr` failures bubbled up with `?`)
+/// are argument problems by construction.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new("invalid_argument", message)
+    }
+}
@@ -0,0 +1,79 @@
+//! Events pushed to the frontend over the Tauri event channel.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bd::discovery::WorkspaceInfo;
+use crate::bd::{Gate, Issue};
+
+/// Tauri event name all dashboard events are emitted under.
+pub const DASHBOARD_EVENT_CHANNEL: &str = "dashboard-event";
+
+/// Tauri event name for targeted updates to explicitly watched issues.
+pub const ISSUE_WATCH_EVENT_CHANNEL: &str = "issue-watch-update";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum DashboardEvent {
+    IssueUpdated(Issue),
+    GateCreated(Gate),
+    GateResolved(Gate),
+    /// Human-readable stats string describing the completed refresh.
+    CacheRefreshed(String),
+    ConnectionChanged { connected: bool },
+    /// The beads registry changed: a workspace appeared, disappeared, or
+    /// its daemon state flipped. `source` names what triggered the refresh.
+    WorkspacesChanged {
+        source: String,
+        workspaces: Vec<WorkspaceInfo>,
+    },
+    Error(String),
+}
+
+impl DashboardEvent {
+    /// Whether this event represents something waiting on the user.
+    ///
+    /// A freshly created gate that is still pending (or blocked) needs a
+    /// decision; a resolved gate, whatever its outcome, no longer does.
+    pub fn is_actionable(&self) -> bool {
+        match self {
+            DashboardEvent::GateCreated(gate) => {
+                gate.status == "pending" || gate.status == "blocked"
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gate(status: &str) -> Gate {
+        serde_json::from_value(json!({
+            "id": "gate-1",
+            "issue_id": "bd-1",
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn newly_created_pending_gate_is_actionable() {
+        assert!(DashboardEvent::GateCreated(gate("pending")).is_actionable());
+        assert!(DashboardEvent::GateCreated(gate("blocked")).is_actionable());
+    }
+
+    #[test]
+    fn already_approved_gate_is_not_actionable() {
+        assert!(!DashboardEvent::GateCreated(gate("approved")).is_actionable());
+    }
+
+    #[test]
+    fn resolved_gate_is_not_actionable() {
+        assert!(!DashboardEvent::GateResolved(gate("approved")).is_actionable());
+        // Even a stale "pending" status on a resolved event isn't a call to
+        // action — the resolution already happened.
+        assert!(!DashboardEvent::GateResolved(gate("pending")).is_actionable());
+    }
+}
@@ -0,0 +1,308 @@
+//! Agent Maestro dashboard backend.
+
+pub mod bd;
+pub mod commands;
+pub mod events;
+pub mod settings;
+pub mod state;
+pub mod tray;
+
+use std::time::Duration;
+
+use tauri::Manager;
+
+use state::AppState;
+
+/// How often the background task refreshes the cache from bd.
+const REFRESH_INTERVAL_SECS: u64 = 60;
+
+/// Override for [`REFRESH_INTERVAL_SECS`], mostly for testing.
+const REFRESH_INTERVAL_ENV: &str = "AGENT_MAESTRO_REFRESH_SECS";
+
+fn refresh_interval() -> Duration {
+    let secs = std::env::var(REFRESH_INTERVAL_ENV)
+        .ok()
+        .and_then(|raw| raw.parse().ok())
+        .filter(|&secs| secs > 0)
+        .unwrap_or(REFRESH_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Keep the cache current even without the activity stream: refresh on an
+/// interval, skipping rounds where the cache is already fresh (the stream
+/// is doing its job) and backing off while bd is unavailable.
+fn spawn_periodic_refresh(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let base = refresh_interval();
+        let mut delay = base;
+        loop {
+            tokio::time::sleep(delay).await;
+            let state = app.state::<AppState>();
+            if !state.beads_cache.read().await.is_stale() {
+                delay = base;
+                continue;
+            }
+            match commands::bd_commands::refresh_from_bd(Some(&app), state.inner()).await {
+                Ok(_) => delay = base,
+                Err(err) => {
+                    delay = (delay * 2).min(base * 8);
+                    tracing::warn!(
+                        "periodic refresh failed ({err}); retrying in {}s",
+                        delay.as_secs()
+                    );
+                }
+            }
+        }
+    });
+}
+
+/// How often the tray badge re-reads the pending-gate count.
+const BADGE_REFRESH_SECS: u64 = 30;
+
+/// Keep the tray badge tracking the pending-gate count even when nothing
+/// user-initiated touches gates. Gate commands and gate activity events
+/// refresh the badge immediately; this loop just catches drift.
+fn spawn_tray_badge_refresh(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(BADGE_REFRESH_SECS)).await;
+            let state = app.state::<AppState>();
+            let pending = state.beads_cache.read().await.get_pending_gates().len();
+            tray::refresh_badge(&app, pending);
+        }
+    });
+}
+
+/// Fetch an issue that an activity event referenced without a payload and
+/// fold it into the cache. Fired from the cache's missing-issue callback,
+/// which is synchronous, hence the detached task.
+fn fetch_missing_issue(app: tauri::AppHandle, id: String) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let client = state.bd_client().await;
+        match client.get_issue(&id).await {
+            Ok(issue) => state.beads_cache.write().await.upsert_issue(issue),
+            Err(err) => tracing::warn!("failed to fetch missing issue {id}: {err}"),
+        }
+    });
+}
+
+/// Start the live `bd activity` stream and keep the cache and the frontend
+/// fed from it. The task outlives any single workspace: a switch is noticed
+/// within a second, the old connection is dropped, and a fresh stream is
+/// started against the new workspace.
+fn spawn_activity_stream(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        use tauri::Emitter;
+
+        loop {
+            let state = app.state::<AppState>();
+            let client = state.bd_client().await;
+            let workspace = client.workspace().to_path_buf();
+
+            // Resume from where the persisted cache left off, and fetch
+            // issues whose events arrive without an embedded payload.
+            let stream = bd::ActivityStream::new(client.bd_path(), &workspace);
+            {
+                let mut cache = state.beads_cache.write().await;
+                *stream.last_seen_handle().lock().unwrap() =
+                    cache.last_event_ts().map(str::to_string);
+                let fetcher_app = app.clone();
+                cache.set_missing_issue_fetcher(std::sync::Arc::new(move |id: &str| {
+                    fetch_missing_issue(fetcher_app.clone(), id.to_string());
+                }));
+            }
+
+            // Out-of-band stream notices go straight to the dashboard.
+            let (notices_tx, mut notices_rx) = tokio::sync::mpsc::channel(16);
+            {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(event) = notices_rx.recv().await {
+                        if let Err(err) = app.emit(events::DASHBOARD_EVENT_CHANNEL, &event) {
+                            tracing::warn!("failed to emit stream notice: {err}");
+                        }
+                    }
+                });
+            }
+            let raw = stream.start(Some(notices_tx), None, bd::StreamConfig::default());
+            let mut events_rx =
+                bd::activity::coalesce_events(raw, bd::activity::COALESCE_WINDOW);
+
+            loop {
+                // Poll with a timeout so a workspace switch is noticed even
+                // while the stream is idle.
+                let received =
+                    tokio::time::timeout(Duration::from_secs(1), events_rx.recv()).await;
+                if state.bd_client().await.workspace() != workspace.as_path() {
+                    // Dropping the receiver tears the old stream down.
+                    break;
+                }
+                match received {
+                    Ok(Some(event)) => {
+                        state.activity_log.write().await.push(event.clone());
+                        let mut cache = state.beads_cache.write().await;
+                        let watched = state.watched_issues.read().await;
+                        let emissions = state::process_activity_event(
+                            &mut cache,
+                            &watched,
+                            &state.activity_paused,
+                            &event,
+                        );
+                        drop(watched);
+                        let gate_changed = emissions.iter().any(|e| {
+                            matches!(
+                                e,
+                                state::Emission::Dashboard(
+                                    events::DashboardEvent::GateCreated(_)
+                                        | events::DashboardEvent::GateResolved(_)
+                                )
+                            )
+                        });
+                        let pending = gate_changed.then(|| cache.get_pending_gates().len());
+                        drop(cache);
+                        if let Some(pending) = pending {
+                            tray::refresh_badge(&app, pending);
+                        }
+                        for emission in emissions {
+                            let result = match emission {
+                                state::Emission::Dashboard(event) => {
+                                    app.emit(events::DASHBOARD_EVENT_CHANNEL, &event)
+                                }
+                                state::Emission::IssueWatch(issue) => {
+                                    app.emit(events::ISSUE_WATCH_EVENT_CHANNEL, &issue)
+                                }
+                            };
+                            if let Err(err) = result {
+                                tracing::warn!("failed to emit activity event: {err}");
+                            }
+                        }
+                    }
+                    Ok(None) => {
+                        // The stream gave up after repeated failures; rest
+                        // before building a new one.
+                        tokio::time::sleep(Duration::from_secs(30)).await;
+                        break;
+                    }
+                    Err(_) => {} // Idle; loop to re-check the workspace.
+                }
+            }
+        }
+    });
+}
+
+/// Emit `WorkspacesChanged` whenever bd rewrites the workspace registry,
+/// so the switcher stays current without polling.
+fn spawn_registry_watch(app: tauri::AppHandle) {
+    let Some(discovery) = bd::WorkspaceDiscovery::new() else {
+        tracing::warn!("no home directory; workspace registry watch disabled");
+        return;
+    };
+    let source = discovery.registry_path().display().to_string();
+    let mut rx = match discovery.watch() {
+        Ok(rx) => rx,
+        Err(err) => {
+            tracing::warn!("failed to watch the workspace registry: {err}");
+            return;
+        }
+    };
+    tauri::async_runtime::spawn(async move {
+        use tauri::Emitter;
+        while let Some(workspaces) = rx.recv().await {
+            tray::rebuild_workspace_menu(&app, &workspaces);
+            let event = events::DashboardEvent::WorkspacesChanged {
+                source: source.clone(),
+                workspaces,
+            };
+            if let Err(err) = app.emit(events::DASHBOARD_EVENT_CHANNEL, &event) {
+                tracing::warn!("failed to emit workspace change: {err}");
+            }
+        }
+    });
+}
+
+pub fn run() {
+    tracing_subscriber::fmt::init();
+
+    tauri::Builder::default()
+        .manage(AppState::new().expect("failed to initialize app state"))
+        .setup(|app| {
+            tray::setup_tray(app)?;
+            spawn_activity_stream(app.handle().clone());
+            spawn_periodic_refresh(app.handle().clone());
+            spawn_registry_watch(app.handle().clone());
+            spawn_tray_badge_refresh(app.handle().clone());
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::bd_commands::list_issues,
+            commands::bd_commands::list_issues_paged,
+            commands::bd_commands::list_issues_filtered,
+            commands::bd_commands::list_issues_sorted,
+            commands::bd_commands::get_issue,
+            commands::bd_commands::create_issue,
+            commands::bd_commands::update_issue_status,
+            commands::bd_commands::bulk_update_status,
+            commands::bd_commands::assign_issue,
+            commands::bd_commands::unassign_issue,
+            commands::bd_commands::close_issue,
+            commands::bd_commands::reopen_issue,
+            commands::bd_commands::delete_issue,
+            commands::bd_commands::claim_issue,
+            commands::bd_commands::get_comments,
+            commands::bd_commands::add_comment,
+            commands::bd_commands::add_label,
+            commands::bd_commands::remove_label,
+            commands::bd_commands::add_dependency,
+            commands::bd_commands::remove_dependency,
+            commands::bd_commands::set_default_assignee,
+            commands::bd_commands::list_gates,
+            commands::bd_commands::get_gate,
+            commands::bd_commands::resolve_gate,
+            commands::bd_commands::reject_gate,
+            commands::bd_commands::bulk_resolve_gates,
+            commands::bd_commands::get_stats,
+            commands::bd_commands::get_workload,
+            commands::bd_commands::refresh_cache,
+            commands::bd_commands::set_status_mapping,
+            commands::bd_commands::get_lead_times,
+            commands::bd_commands::get_next_action,
+            commands::bd_commands::get_cache_age_secs,
+            commands::bd_commands::get_staleness_config,
+            commands::bd_commands::set_staleness_config,
+            commands::bd_commands::check_health,
+            commands::bd_commands::get_bd_health,
+            commands::bd_commands::get_health_history,
+            commands::bd_commands::search_issues,
+            commands::bd_commands::search_issues_advanced,
+            commands::bd_commands::list_ready,
+            commands::bd_commands::list_blocked,
+            commands::bd_commands::get_stale_issues,
+            commands::bd_commands::get_pending_gates,
+            commands::bd_commands::get_epic_status,
+            commands::bd_commands::get_epic_overview,
+            commands::bd_commands::list_epics,
+            commands::bd_commands::get_dag,
+            commands::bd_commands::get_dag_with_summary,
+            commands::bd_commands::has_cycles,
+            commands::bd_commands::get_critical_path,
+            commands::bd_commands::get_issue_neighborhood,
+            commands::bd_commands::export_epic_markdown,
+            commands::bd_commands::export_issues_jsonl,
+            commands::bd_commands::export_issues_csv,
+            commands::bd_commands::export_dag_dot,
+            commands::bd_commands::export_dag_mermaid,
+            commands::bd_commands::switch_workspace,
+            commands::bd_commands::list_workspaces,
+            commands::bd_commands::register_workspace,
+            commands::bd_commands::restart_bd_daemon,
+            commands::bd_commands::is_offline,
+            commands::bd_commands::get_recent_activity,
+            commands::bd_commands::pause_activity,
+            commands::bd_commands::resume_activity,
+            commands::bd_commands::watch_issue,
+            commands::bd_commands::unwatch_issue,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
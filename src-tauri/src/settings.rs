@@ -0,0 +1,68 @@
+//! Small persisted settings file (`dirs::config_dir()/agent-maestro/
+//! settings.json`). Deliberately separate from the domain cache: losing it
+//! costs a preference, not data.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Workspace that was active when the app last ran.
+    #[serde(default)]
+    pub last_workspace: Option<PathBuf>,
+}
+
+impl Settings {
+    /// Default location of the settings file; `None` when the platform has
+    /// no config dir.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("agent-maestro").join("settings.json"))
+    }
+
+    /// Load from `path`. A missing file is the first-run case and a corrupt
+    /// one is not worth failing startup over — both come back as defaults.
+    pub fn load(path: &Path) -> Self {
+        let Ok(bytes) = std::fs::read(path) else {
+            return Self::default();
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+            tracing::warn!("ignoring corrupt settings file {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("settings.json");
+
+        let settings = Settings {
+            last_workspace: Some(PathBuf::from("/work/beads")),
+        };
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path), settings);
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        assert_eq!(Settings::load(&path), Settings::default());
+
+        std::fs::write(&path, b"{not json").unwrap();
+        assert_eq!(Settings::load(&path), Settings::default());
+    }
+}
@@ -0,0 +1,404 @@
+//! Shared application state managed by Tauri.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::bd::{ActivityEvent, BdClient, BdResult, BeadsCache, HealthChecker, Issue};
+use crate::events::DashboardEvent;
+use crate::settings::Settings;
+
+pub struct AppState {
+    /// Swapped atomically when the user switches workspaces.
+    bd_client: RwLock<Arc<BdClient>>,
+    pub beads_cache: Arc<RwLock<BeadsCache>>,
+    /// Issue IDs the frontend asked for targeted updates on.
+    pub watched_issues: Arc<RwLock<HashSet<String>>>,
+    /// While set, the forwarding loop consumes activity events without
+    /// applying or emitting them. The stream child stays alive and draining;
+    /// a resume reconciles with a full refresh.
+    pub activity_paused: Arc<AtomicBool>,
+    /// Workspace health probes; behind a lock so the age bound is tunable.
+    pub health_checker: Arc<RwLock<HealthChecker>>,
+    /// Set while bd is unreachable and reads are served from the cache;
+    /// drives the frontend's offline banner.
+    pub offline: Arc<AtomicBool>,
+    /// Session-only tail of recent activity events for the UI's feed.
+    pub activity_log: Arc<RwLock<ActivityLog>>,
+}
+
+impl AppState {
+    pub fn new() -> BdResult<Self> {
+        let workspace = Self::resolve_workspace()?;
+        let client = BdClient::new(&workspace)?;
+        Ok(Self {
+            bd_client: RwLock::new(Arc::new(client)),
+            beads_cache: Arc::new(RwLock::new(BeadsCache::new())),
+            watched_issues: Arc::new(RwLock::new(HashSet::new())),
+            activity_paused: Arc::new(AtomicBool::new(false)),
+            health_checker: Arc::new(RwLock::new(HealthChecker::new())),
+            offline: Arc::new(AtomicBool::new(false)),
+            activity_log: Arc::new(RwLock::new(ActivityLog::new())),
+        })
+    }
+
+    /// Pick the initial workspace: `BEADS_WORKSPACE`, then
+    /// `AGENT_MAESTRO_WORKSPACE`, then the current directory. Desktop
+    /// launchers start the app with cwd `/`, so the env vars are the only
+    /// reliable way to pin a workspace there.
+    fn resolve_workspace() -> std::io::Result<PathBuf> {
+        for var in ["BEADS_WORKSPACE", "AGENT_MAESTRO_WORKSPACE"] {
+            if let Some(path) = std::env::var_os(var).map(PathBuf::from) {
+                if path.is_dir() {
+                    tracing::info!("workspace from ${var}: {}", path.display());
+                    return Ok(path);
+                }
+                tracing::warn!(
+                    "${var} points at a missing directory, ignoring: {}",
+                    path.display()
+                );
+            }
+        }
+        if let Some(path) = Settings::default_path()
+            .map(|p| Settings::load(&p))
+            .and_then(|s| s.last_workspace)
+        {
+            if path.is_dir() {
+                tracing::info!("workspace from saved settings: {}", path.display());
+                return Ok(path);
+            }
+            tracing::warn!(
+                "saved workspace no longer exists, ignoring: {}",
+                path.display()
+            );
+        }
+        let cwd = std::env::current_dir()?;
+        tracing::info!("workspace from current dir: {}", cwd.display());
+        Ok(cwd)
+    }
+
+    pub fn pause_activity(&self) {
+        self.activity_paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Clear the paused flag. Returns whether the stream was actually
+    /// paused, in which case the caller should reconcile the cache with a
+    /// full refresh (events were dropped).
+    pub fn resume_activity(&self) -> bool {
+        self.activity_paused.swap(false, Ordering::SeqCst)
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst)
+    }
+
+    /// Record the latest connectivity observation. Returns whether the flag
+    /// actually flipped, in which case the caller should announce the change.
+    pub fn set_offline(&self, offline: bool) -> bool {
+        self.offline.swap(offline, Ordering::SeqCst) != offline
+    }
+
+    pub async fn bd_client(&self) -> Arc<BdClient> {
+        self.bd_client.read().await.clone()
+    }
+
+    /// Point the app at a different workspace. Writes still queued on the
+    /// old client are cancelled first so nothing conceptually aimed at the
+    /// old workspace runs after the switch.
+    pub async fn switch_bd_client(&self, workspace: PathBuf) -> BdResult<()> {
+        let client = BdClient::new(&workspace)?;
+        let mut guard = self.bd_client.write().await;
+        guard.close_writes();
+        *guard = Arc::new(client);
+        drop(guard);
+
+        // Remember the choice for the next launch. Best-effort: a read-only
+        // config dir shouldn't fail the switch itself.
+        if let Some(path) = Settings::default_path() {
+            let mut settings = Settings::load(&path);
+            settings.last_workspace = Some(workspace);
+            if let Err(err) = settings.save(&path) {
+                tracing::warn!("failed to persist workspace choice: {err}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How many activity events the session feed retains.
+pub const ACTIVITY_LOG_CAPACITY: usize = 200;
+
+/// Bounded in-memory tail of the activity stream, backing the UI's feed.
+/// Deliberately not persisted: after a restart the feed starts fresh, the
+/// cache's event cursor handles continuity.
+pub struct ActivityLog {
+    events: VecDeque<ActivityEvent>,
+    capacity: usize,
+}
+
+impl ActivityLog {
+    pub fn new() -> Self {
+        Self::with_capacity(ACTIVITY_LOG_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, event: ActivityEvent) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// The most recent events, newest first, at most `limit` of them.
+    pub fn recent(&self, limit: usize) -> Vec<ActivityEvent> {
+        self.events.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+impl Default for ActivityLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Something the event-forwarding loop should emit to the frontend.
+#[derive(Debug)]
+pub enum Emission {
+    /// Broadcast on the shared dashboard channel.
+    Dashboard(DashboardEvent),
+    /// Targeted `issue-watch-update` for a watched issue.
+    IssueWatch(Issue),
+}
+
+/// Apply one activity event to the cache and work out what to emit.
+///
+/// Pure with respect to the UI so the emission logic is testable; the caller
+/// turns [`Emission`]s into actual Tauri events.
+pub fn process_activity_event(
+    cache: &mut BeadsCache,
+    watched: &HashSet<String>,
+    paused: &AtomicBool,
+    event: &ActivityEvent,
+) -> Vec<Emission> {
+    if paused.load(Ordering::SeqCst) {
+        // Consumed but deliberately not applied; the resume path reconciles.
+        return Vec::new();
+    }
+    cache.apply_event(event);
+
+    let mut emissions = Vec::new();
+    match event.event_type.as_str() {
+        "issue.created" | "issue.updated" | "issue.deleted" => {
+            if let Some(issue) = event
+                .issue_id
+                .as_deref()
+                .and_then(|id| cache.get_issue(id))
+                .cloned()
+            {
+                if watched.contains(&issue.id) {
+                    emissions.push(Emission::IssueWatch(issue.clone()));
+                }
+                emissions.push(Emission::Dashboard(DashboardEvent::IssueUpdated(issue)));
+            }
+        }
+        "gate.created" | "gate.updated" | "gate.resolved" => {
+            if let Some(gate) = event
+                .extra
+                .get("gate")
+                .cloned()
+                .and_then(|v| serde_json::from_value(v).ok())
+            {
+                let event = if event.event_type == "gate.resolved" {
+                    DashboardEvent::GateResolved(gate)
+                } else {
+                    DashboardEvent::GateCreated(gate)
+                };
+                emissions.push(Emission::Dashboard(event));
+            }
+        }
+        _ => {}
+    }
+    emissions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn update_event(id: &str) -> ActivityEvent {
+        serde_json::from_value(json!({
+            "event_type": "issue.updated",
+            "issue_id": id,
+            "issue": {"id": id, "title": "t", "status": "open"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn activity_log_evicts_oldest_past_capacity() {
+        let mut log = ActivityLog::with_capacity(3);
+        for id in ["bd-1", "bd-2", "bd-3", "bd-4"] {
+            log.push(update_event(id));
+        }
+        let ids: Vec<Option<String>> = log
+            .recent(10)
+            .into_iter()
+            .map(|e| e.issue_id)
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                Some("bd-4".to_string()),
+                Some("bd-3".to_string()),
+                Some("bd-2".to_string())
+            ],
+            "newest first, bd-1 evicted"
+        );
+    }
+
+    #[test]
+    fn activity_log_honors_the_requested_limit() {
+        let mut log = ActivityLog::new();
+        for id in ["bd-1", "bd-2", "bd-3"] {
+            log.push(update_event(id));
+        }
+        let recent = log.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].issue_id.as_deref(), Some("bd-3"));
+    }
+
+    #[test]
+    fn watched_issue_gets_targeted_emission() {
+        let mut cache = BeadsCache::new();
+        let watched: HashSet<String> = ["bd-1".to_string()].into_iter().collect();
+        let paused = AtomicBool::new(false);
+
+        let emissions =
+            process_activity_event(&mut cache, &watched, &paused, &update_event("bd-1"));
+        assert!(emissions
+            .iter()
+            .any(|e| matches!(e, Emission::IssueWatch(issue) if issue.id == "bd-1")));
+        assert!(emissions
+            .iter()
+            .any(|e| matches!(e, Emission::Dashboard(DashboardEvent::IssueUpdated(_)))));
+    }
+
+    #[test]
+    fn unwatched_issue_only_gets_broadcast() {
+        let mut cache = BeadsCache::new();
+        let watched: HashSet<String> = ["bd-other".to_string()].into_iter().collect();
+        let paused = AtomicBool::new(false);
+
+        let emissions =
+            process_activity_event(&mut cache, &watched, &paused, &update_event("bd-1"));
+        assert!(!emissions.iter().any(|e| matches!(e, Emission::IssueWatch(_))));
+        assert_eq!(emissions.len(), 1);
+    }
+
+    #[test]
+    fn gate_created_event_emits_with_the_gate_payload() {
+        let mut cache = BeadsCache::new();
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "gate.created",
+            "gate": {"id": "gate-1", "issue_id": "bd-1", "status": "pending"}
+        }))
+        .unwrap();
+
+        let emissions =
+            process_activity_event(&mut cache, &HashSet::new(), &AtomicBool::new(false), &event);
+        assert!(emissions.iter().any(|e| matches!(
+            e,
+            Emission::Dashboard(DashboardEvent::GateCreated(gate))
+                if gate.id == "gate-1" && gate.status == "pending"
+        )));
+        // The cache picked the gate up too.
+        assert_eq!(cache.get_pending_gates().len(), 1);
+    }
+
+    #[test]
+    fn gate_resolved_event_emits_with_the_resolved_payload() {
+        let mut cache = BeadsCache::new();
+        let event: ActivityEvent = serde_json::from_value(json!({
+            "event_type": "gate.resolved",
+            "gate": {"id": "gate-1", "issue_id": "bd-1", "status": "approved"}
+        }))
+        .unwrap();
+
+        let emissions =
+            process_activity_event(&mut cache, &HashSet::new(), &AtomicBool::new(false), &event);
+        assert!(emissions.iter().any(|e| matches!(
+            e,
+            Emission::Dashboard(DashboardEvent::GateResolved(gate)) if gate.status == "approved"
+        )));
+        assert!(cache.get_pending_gates().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stream_events_flow_through_processing_into_the_cache() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("bd");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             echo '{\"event_type\":\"issue.created\",\"issue_id\":\"bd-1\",\
+             \"issue\":{\"id\":\"bd-1\",\"title\":\"one\",\"status\":\"open\"}}'\n\
+             echo '{\"event_type\":\"issue.updated\",\"issue_id\":\"bd-2\",\
+             \"issue\":{\"id\":\"bd-2\",\"title\":\"two\",\"status\":\"open\"}}'\n\
+             sleep 5\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let stream = crate::bd::ActivityStream::new(&script, dir.path());
+        let mut rx = stream.start(None, None, crate::bd::StreamConfig::default());
+
+        let mut cache = BeadsCache::new();
+        let watched = HashSet::new();
+        let paused = AtomicBool::new(false);
+        for _ in 0..2 {
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("timed out waiting for stream event")
+                .expect("stream closed");
+            let emissions = process_activity_event(&mut cache, &watched, &paused, &event);
+            assert!(emissions
+                .iter()
+                .any(|e| matches!(e, Emission::Dashboard(DashboardEvent::IssueUpdated(_)))));
+        }
+        assert!(cache.get_issue("bd-1").is_some());
+        assert!(cache.get_issue("bd-2").is_some());
+    }
+
+    #[test]
+    fn paused_events_neither_apply_nor_emit() {
+        let mut cache = BeadsCache::new();
+        let watched = HashSet::new();
+        let paused = AtomicBool::new(true);
+
+        let emissions =
+            process_activity_event(&mut cache, &watched, &paused, &update_event("bd-1"));
+        assert!(emissions.is_empty());
+        assert!(cache.get_issue("bd-1").is_none());
+
+        // After resuming, events flow again.
+        paused.store(false, Ordering::SeqCst);
+        let emissions =
+            process_activity_event(&mut cache, &watched, &paused, &update_event("bd-1"));
+        assert_eq!(emissions.len(), 1);
+        assert!(cache.get_issue("bd-1").is_some());
+    }
+}
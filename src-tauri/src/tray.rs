@@ -0,0 +1,277 @@
+//! System tray icon and menu.
+//!
+//! The tray's job is ambient awareness: the approval-queue item carries the
+//! live pending-gate count, and the workspace submenu switches workspaces
+//! without a trip through the main window.
+
+use std::path::PathBuf;
+
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::bd::discovery::WorkspaceInfo;
+use crate::bd::WorkspaceDiscovery;
+
+/// Most workspaces shown directly in the tray submenu; the rest hide
+/// behind "More…".
+const MAX_TRAY_WORKSPACES: usize = 8;
+
+/// Frontend event asking the full workspace picker to open.
+const WORKSPACE_PICKER_EVENT: &str = "open-workspace-picker";
+
+/// Handles the tray keeps in managed state: the icon (to swap menus), the
+/// menu item whose text carries the live count, and the notify tracker.
+pub struct TrayState {
+    tray: tauri::tray::TrayIcon,
+    approval_item: std::sync::Mutex<MenuItem<tauri::Wry>>,
+    tracker: std::sync::Mutex<BadgeTracker>,
+}
+
+/// Decides when a new-approval notification is warranted: only when the
+/// pending count rises. Periodic ticks at a steady count stay silent, so
+/// the user isn't re-notified about gates they're already ignoring.
+#[derive(Debug, Default)]
+pub struct BadgeTracker {
+    last: usize,
+}
+
+impl BadgeTracker {
+    /// Record the latest count; returns whether it increased.
+    pub fn observe(&mut self, pending: usize) -> bool {
+        let increased = pending > self.last;
+        self.last = pending;
+        increased
+    }
+
+    /// The most recently observed count.
+    pub fn last(&self) -> usize {
+        self.last
+    }
+}
+
+fn badge_text(pending: usize) -> String {
+    format!("Approval Queue ({pending} pending)")
+}
+
+/// Display label for one workspace entry: its registered name when bd has
+/// one, otherwise the directory name, otherwise the full path.
+fn workspace_label(workspace: &WorkspaceInfo) -> String {
+    if let Some(name) = &workspace.name {
+        return name.clone();
+    }
+    workspace
+        .workspace_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| workspace.workspace_path.display().to_string())
+}
+
+/// The slice of workspaces that fits in the tray directly.
+fn tray_workspaces(all: &[WorkspaceInfo]) -> &[WorkspaceInfo] {
+    &all[..all.len().min(MAX_TRAY_WORKSPACES)]
+}
+
+/// Build the whole tray menu. Returns the approval item alongside the menu
+/// because menu items can't be looked up again once built.
+fn build_menu<M: Manager<tauri::Wry>>(
+    app: &M,
+    workspaces: &[WorkspaceInfo],
+    pending: usize,
+) -> tauri::Result<(Menu<tauri::Wry>, MenuItem<tauri::Wry>)> {
+    let approval_item =
+        MenuItem::with_id(app, "approval-queue", badge_text(pending), true, None::<&str>)?;
+
+    let mut entries: Vec<MenuItem<tauri::Wry>> = Vec::new();
+    for workspace in tray_workspaces(workspaces) {
+        entries.push(MenuItem::with_id(
+            app,
+            format!("ws:{}", workspace.workspace_path.display()),
+            workspace_label(workspace),
+            workspace.exists,
+            None::<&str>,
+        )?);
+    }
+    let more = MenuItem::with_id(app, "ws-more", "More…", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let mut submenu_items: Vec<&dyn tauri::menu::IsMenuItem<tauri::Wry>> = Vec::new();
+    for entry in &entries {
+        submenu_items.push(entry);
+    }
+    submenu_items.push(&separator);
+    submenu_items.push(&more);
+    let switcher = Submenu::with_items(app, "Switch Workspace", true, &submenu_items)?;
+
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&approval_item, &switcher, &quit])?;
+    Ok((menu, approval_item))
+}
+
+/// Build the tray and stash the handles needed later in managed state.
+pub fn setup_tray(app: &tauri::App) -> tauri::Result<()> {
+    let workspaces = WorkspaceDiscovery::new()
+        .map(|discovery| discovery.discover().unwrap_or_default())
+        .unwrap_or_default();
+    let (menu, approval_item) = build_menu(app, &workspaces, 0)?;
+
+    let tray = TrayIconBuilder::with_id("agent-maestro")
+        .menu(&menu)
+        .on_menu_event(|app, event| {
+            let id = event.id.as_ref();
+            if id == "quit" {
+                app.exit(0);
+            } else if id == "ws-more" {
+                focus_main_window(app);
+                if let Err(err) = app.emit(WORKSPACE_PICKER_EVENT, ()) {
+                    tracing::warn!("failed to request the workspace picker: {err}");
+                }
+            } else if let Some(path) = id.strip_prefix("ws:") {
+                switch_to_workspace(app.clone(), PathBuf::from(path));
+            }
+        })
+        .build(app)?;
+
+    app.manage(TrayState {
+        tray,
+        approval_item: std::sync::Mutex::new(approval_item),
+        tracker: std::sync::Mutex::new(BadgeTracker::default()),
+    });
+    Ok(())
+}
+
+/// Swap in a menu reflecting the latest workspace list. The approval item
+/// is rebuilt too — menu items can't move between menus — seeded with the
+/// last observed count so the text doesn't flicker back to zero.
+pub fn rebuild_workspace_menu(app: &AppHandle, workspaces: &[WorkspaceInfo]) {
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let pending = state.tracker.lock().unwrap().last();
+    match build_menu(app, workspaces, pending) {
+        Ok((menu, approval_item)) => {
+            if let Err(err) = state.tray.set_menu(Some(menu)) {
+                tracing::warn!("failed to replace the tray menu: {err}");
+                return;
+            }
+            *state.approval_item.lock().unwrap() = approval_item;
+        }
+        Err(err) => tracing::warn!("failed to rebuild the tray menu: {err}"),
+    }
+}
+
+/// Tray-initiated switch: same path as the `switch_workspace` command,
+/// then bring the window forward so the user sees the result.
+fn switch_to_workspace(app: AppHandle, workspace: PathBuf) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<crate::state::AppState>();
+        if let Err(err) = state.switch_bd_client(workspace).await {
+            tracing::warn!("tray workspace switch failed: {err}");
+            return;
+        }
+        focus_main_window(&app);
+    });
+}
+
+fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// One-stop badge refresh: update the tray text (and dock badge), and fire
+/// a native notification only when the count rose since the last look.
+pub fn refresh_badge(app: &AppHandle, pending: usize) {
+    update_tray_badge(app, pending);
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let increased = state.tracker.lock().unwrap().observe(pending);
+    if increased {
+        notify_new_approval(app, pending);
+    }
+}
+
+/// Native notification for newly pending approvals.
+pub fn notify_new_approval(app: &AppHandle, pending: usize) {
+    use tauri_plugin_notification::NotificationExt;
+
+    let body = if pending == 1 {
+        "1 gate is waiting for review".to_string()
+    } else {
+        format!("{pending} gates are waiting for review")
+    };
+    if let Err(err) = app
+        .notification()
+        .builder()
+        .title("Approval needed")
+        .body(body)
+        .show()
+    {
+        tracing::warn!("failed to show approval notification: {err}");
+    }
+}
+
+/// Reflect `pending` in the tray menu text and, on macOS, the dock badge
+/// (cleared when nothing is pending).
+pub fn update_tray_badge(app: &AppHandle, pending: usize) {
+    if let Some(state) = app.try_state::<TrayState>() {
+        let item = state.approval_item.lock().unwrap();
+        if let Err(err) = item.set_text(badge_text(pending)) {
+            tracing::warn!("failed to update tray badge: {err}");
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let count = (pending > 0).then_some(pending as i64);
+        if let Err(err) = app.set_badge_count(count) {
+            tracing::warn!("failed to update dock badge: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badge_text_carries_the_count() {
+        assert_eq!(badge_text(0), "Approval Queue (0 pending)");
+        assert_eq!(badge_text(12), "Approval Queue (12 pending)");
+    }
+
+    #[test]
+    fn tracker_notifies_only_on_increases() {
+        let mut tracker = BadgeTracker::default();
+        assert!(tracker.observe(2), "first nonzero count should notify");
+        assert!(!tracker.observe(2), "steady count must stay silent");
+        assert!(!tracker.observe(1), "a drop must stay silent");
+        assert!(tracker.observe(3), "a rise notifies again");
+        assert!(!tracker.observe(0));
+        assert!(tracker.observe(1));
+    }
+
+    fn workspace(path: &str, name: Option<&str>) -> WorkspaceInfo {
+        WorkspaceInfo {
+            workspace_path: PathBuf::from(path),
+            name: name.map(str::to_string),
+            exists: true,
+            daemon_running: false,
+        }
+    }
+
+    #[test]
+    fn labels_prefer_name_then_directory() {
+        assert_eq!(workspace_label(&workspace("/work/a", Some("alpha"))), "alpha");
+        assert_eq!(workspace_label(&workspace("/work/beads", None)), "beads");
+    }
+
+    #[test]
+    fn submenu_is_capped_at_eight_entries() {
+        let many: Vec<WorkspaceInfo> = (0..12)
+            .map(|i| workspace(&format!("/work/{i}"), None))
+            .collect();
+        assert_eq!(tray_workspaces(&many).len(), MAX_TRAY_WORKSPACES);
+        assert_eq!(tray_workspaces(&many[..3]).len(), 3);
+    }
+}